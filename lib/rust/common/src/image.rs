@@ -0,0 +1,63 @@
+// Checks on image links that don't download the images, and swapping the
+// link of an image request for a working mirror from `modify_image_request`.
+use aidoku::std::{
+	net::{HttpMethod, Request},
+	String, ValueRef,
+};
+
+#[link(wasm_import_module = "net")]
+extern "C" {
+	#[link_name = "get_status_code"]
+	fn request_get_status_code(rd: i32) -> i32;
+	#[link_name = "get_url"]
+	fn request_get_url(rd: i32) -> i32;
+	#[link_name = "set_url"]
+	fn request_set_url(rd: i32, url: *const u8, url_len: usize);
+}
+
+fn send(request: Request, headers: &[(&str, &str)]) -> (Request, i32) {
+	let request = headers
+		.iter()
+		.fold(request, |request, &(key, value)| request.header(key, value));
+	request.send();
+	let status = unsafe { request_get_status_code(request.0) };
+	(request, status)
+}
+
+// broken links on some cdns answer with an html page instead of an error
+fn is_image(request: &Request) -> bool {
+	request
+		.get_header("Content-Type")
+		.map(|content_type| !content_type.read().starts_with("text/"))
+		.unwrap_or(true)
+}
+
+/// Whether `url` answers with an image when requested with `headers`.
+///
+/// Only the headers are fetched, or the first byte of the image for hosts
+/// that don't allow `HEAD` requests.
+pub fn image_available(url: &str, headers: &[(&str, &str)]) -> bool {
+	let (request, status) = send(Request::new(url, HttpMethod::Head), headers);
+	match status {
+		200..=299 => is_image(&request),
+		405 | 501 => {
+			let (request, status) = send(
+				Request::new(url, HttpMethod::Get).header("Range", "bytes=0-0"),
+				headers,
+			);
+			(200..=299).contains(&status) && is_image(&request)
+		}
+		_ => false,
+	}
+}
+
+/// The link an image request was made for.
+pub fn request_url(request: &Request) -> Option<String> {
+	let rid = unsafe { request_get_url(request.0) };
+	ValueRef::new(rid).as_string().map(|url| url.read()).ok()
+}
+
+/// Points an image request at another link, headers are kept.
+pub fn set_request_url(request: &Request, url: &str) {
+	unsafe { request_set_url(request.0, url.as_ptr(), url.len()) };
+}
@@ -0,0 +1,3 @@
+#![no_std]
+pub mod image;
+pub mod text;
@@ -0,0 +1,84 @@
+// Chapter bodies of novels, split into pages of text.
+use aidoku::{
+	std::{html::Node, String, Vec},
+	Page,
+};
+
+/// Characters of text shown per page.
+pub const TEXT_PAGE_LENGTH: usize = 4000;
+
+/// Text of a node with its `<br>` tags kept as line breaks.
+pub fn text_with_newlines(node: Node) -> String {
+	let html = node.html().read();
+	if !String::from(html.trim()).is_empty() {
+		Node::new_fragment(
+			node.html()
+				.read()
+				.replace("<br>", "{{ .LINEBREAK }}")
+				.as_bytes(),
+		)
+		.text()
+		.read()
+		.replace("{{ .LINEBREAK }}", "\n")
+	} else {
+		String::new()
+	}
+}
+
+/// Paragraphs of a chapter body, from its `<p>` elements or from its lines
+/// when it has none. Blank paragraphs are left out.
+pub fn paragraphs(content: Node) -> Vec<String> {
+	let elems = content.select("p");
+	let paragraphs = if elems.array().len() > 0 {
+		elems
+			.array()
+			.map(|elem| text_with_newlines(elem.as_node()))
+			.collect::<Vec<_>>()
+	} else {
+		text_with_newlines(content)
+			.split('\n')
+			.map(String::from)
+			.collect::<Vec<_>>()
+	};
+	paragraphs
+		.iter()
+		.map(|paragraph| String::from(paragraph.trim()))
+		.filter(|paragraph| !paragraph.is_empty())
+		.collect()
+}
+
+/// Joins paragraphs into pages of about `max_len` characters, only breaking
+/// between paragraphs.
+pub fn split_text_pages(paragraphs: Vec<String>, max_len: usize) -> Vec<String> {
+	let mut pages: Vec<String> = Vec::new();
+	let mut current = String::new();
+	for paragraph in paragraphs {
+		if !current.is_empty() && current.chars().count() + paragraph.chars().count() > max_len {
+			pages.push(current);
+			current = String::new();
+		}
+		if !current.is_empty() {
+			current.push_str("\n\n");
+		}
+		current.push_str(&paragraph);
+	}
+	if !current.is_empty() {
+		pages.push(current);
+	}
+	pages
+}
+
+/// Text pages of a chapter's paragraphs, `TEXT_PAGE_LENGTH` characters or
+/// so each.
+pub fn text_pages(paragraphs: Vec<String>) -> Vec<Page> {
+	split_text_pages(paragraphs, TEXT_PAGE_LENGTH)
+		.into_iter()
+		.enumerate()
+		.map(|(index, text)| Page {
+			index: index as i32,
+			url: String::new(),
+			base64: String::new(),
+			text,
+		})
+		.collect()
+}
@@ -0,0 +1,242 @@
+// Solver for the "set a cookie then reload" anti-bot pages (vinahost and
+// similar), which answer the first request with a tiny script like
+// `document.cookie="D1N="+"a1b2";window.location.reload(true);`.
+#![no_std]
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+
+// challenge pages are a few hundred bytes, anything bigger is a real page
+const MAX_CHALLENGE_SIZE: usize = 16384;
+
+enum Value {
+	Str(String),
+	Num(f64),
+}
+
+impl Value {
+	fn into_string(self) -> String {
+		match self {
+			Value::Str(str) => str,
+			Value::Num(num) if num == (num as i64) as f64 => format!("{}", num as i64),
+			Value::Num(num) => format!("{}", num),
+		}
+	}
+}
+
+struct Parser<'a> {
+	src: &'a str,
+	pos: usize,
+	vars: &'a [(String, String)],
+}
+
+impl<'a> Parser<'a> {
+	fn rest(&self) -> &'a str {
+		&self.src[self.pos..]
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = self.rest();
+		self.pos += rest.len() - rest.trim_start().len();
+	}
+
+	fn eat(&mut self, token: &str) -> bool {
+		self.skip_whitespace();
+		if self.rest().starts_with(token) {
+			self.pos += token.len();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn identifier(&mut self) -> Option<&'a str> {
+		self.skip_whitespace();
+		let rest = self.rest();
+		let len = rest
+			.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '.'))
+			.unwrap_or(rest.len());
+		if len == 0 {
+			return None;
+		}
+		self.pos += len;
+		Some(&rest[..len])
+	}
+
+	fn number(&mut self) -> Option<f64> {
+		self.skip_whitespace();
+		let rest = self.rest();
+		if let Some(hex) = rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+			let len = hex
+				.find(|c: char| !c.is_ascii_hexdigit())
+				.unwrap_or(hex.len());
+			let value = u64::from_str_radix(&hex[..len], 16).ok()?;
+			self.pos += 2 + len;
+			return Some(value as f64);
+		}
+		let len = rest
+			.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+			.unwrap_or(rest.len());
+		let value = rest[..len].parse::<f64>().ok()?;
+		self.pos += len;
+		Some(value)
+	}
+
+	fn string(&mut self, quote: char) -> Option<String> {
+		let mut result = String::new();
+		let mut chars = self.rest().char_indices().skip(1);
+		while let Some((at, c)) = chars.next() {
+			match c {
+				'\\' => {
+					let (_, escaped) = chars.next()?;
+					match escaped {
+						'x' | 'u' => {
+							let len = if escaped == 'x' { 2 } else { 4 };
+							let start = at + 2;
+							let code = self.rest().get(start..start + len)?;
+							let code = u32::from_str_radix(code, 16).ok()?;
+							result.push(char::from_u32(code)?);
+							for _ in 0..len {
+								chars.next();
+							}
+						}
+						'n' => result.push('\n'),
+						't' => result.push('\t'),
+						other => result.push(other),
+					}
+				}
+				c if c == quote => {
+					self.pos += at + 1;
+					return Some(result);
+				}
+				c => result.push(c),
+			}
+		}
+		None
+	}
+
+	fn term(&mut self) -> Option<Value> {
+		self.skip_whitespace();
+		let next = self.rest().chars().next()?;
+		match next {
+			'"' | '\'' => self.string(next).map(Value::Str),
+			'(' => {
+				self.pos += 1;
+				let value = self.expression()?;
+				self.eat(")").then_some(value)
+			}
+			'0'..='9' => self.number().map(Value::Num),
+			_ => {
+				let name = self.identifier()?;
+				if name == "String.fromCharCode" {
+					if !self.eat("(") {
+						return None;
+					}
+					let mut result = String::new();
+					loop {
+						let code = self.expression()?;
+						let code = match code {
+							Value::Num(num) => num as u32,
+							Value::Str(str) => str.parse::<u32>().ok()?,
+						};
+						result.push(char::from_u32(code)?);
+						if self.eat(")") {
+							break;
+						}
+						if !self.eat(",") {
+							return None;
+						}
+					}
+					Some(Value::Str(result))
+				} else {
+					self.vars
+						.iter()
+						.rev()
+						.find(|(var, _)| var == name)
+						.map(|(_, value)| Value::Str(value.clone()))
+				}
+			}
+		}
+	}
+
+	fn expression(&mut self) -> Option<Value> {
+		let mut value = self.term()?;
+		while self.eat("+") {
+			let next = self.term()?;
+			value = match (value, next) {
+				(Value::Num(a), Value::Num(b)) => Value::Num(a + b),
+				(a, b) => Value::Str(a.into_string() + &b.into_string()),
+			};
+		}
+		Some(value)
+	}
+}
+
+// evaluate the expression assigned at `pos`, e.g. right after `document.cookie`
+fn assigned_value(src: &str, pos: usize, vars: &[(String, String)]) -> Option<String> {
+	let mut parser = Parser { src, pos, vars };
+	if !parser.eat("=") || parser.rest().starts_with('=') {
+		return None;
+	}
+	parser.expression().map(Value::into_string)
+}
+
+// whether the response is a cookie challenge instead of the requested page
+pub fn is_challenge(page: &str) -> bool {
+	page.len() < MAX_CHALLENGE_SIZE
+		&& page.contains("document.cookie")
+		&& (page.contains("location.reload")
+			|| page.contains("location.href")
+			|| page.contains("location.replace"))
+}
+
+// evaluate the challenge script and return the cookie header it sets,
+// `None` if the page is not a challenge or uses an unknown variant
+pub fn solve(page: &str) -> Option<String> {
+	if !is_challenge(page) {
+		return None;
+	}
+	let mut vars: Vec<(String, String)> = Vec::new();
+	let mut cookies: Vec<String> = Vec::new();
+	let mut pos = 0;
+	while pos < page.len() {
+		let rest = &page[pos..];
+		let next = ["var ", "let ", "const ", "document.cookie"]
+			.iter()
+			.filter_map(|keyword| rest.find(keyword).map(|at| (at, *keyword)))
+			.min_by_key(|(at, _)| *at);
+		let (at, keyword) = match next {
+			Some(next) => next,
+			None => break,
+		};
+		let start = pos + at + keyword.len();
+		pos = start;
+		if keyword == "document.cookie" {
+			if let Some(value) = assigned_value(page, start, &vars) {
+				// drop attributes like `; path=/; max-age=…`
+				let cookie = value.split(';').next().unwrap_or_default().trim();
+				if cookie.contains('=') {
+					cookies.push(String::from(cookie));
+				}
+			}
+		} else {
+			let mut parser = Parser {
+				src: page,
+				pos: start,
+				vars: &vars,
+			};
+			if let Some(name) = parser.identifier() {
+				let name = String::from(name);
+				let value_start = parser.pos;
+				if let Some(value) = assigned_value(page, value_start, &vars) {
+					vars.push((name, value));
+				}
+			}
+		}
+	}
+	if cookies.is_empty() {
+		None
+	} else {
+		Some(cookies.join("; "))
+	}
+}
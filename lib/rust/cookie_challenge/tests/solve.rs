@@ -0,0 +1,37 @@
+use cookie_challenge::{is_challenge, solve};
+
+#[test]
+fn solves_concatenated_strings() {
+	let page = include_str!("fixtures/vinahost.html");
+	assert!(is_challenge(page));
+	assert_eq!(
+		solve(page).as_deref(),
+		Some("D1N=3f2d5e0c9b7a41e6b8c2d4f1a0e9c7b5")
+	);
+}
+
+#[test]
+fn solves_char_codes_and_escapes() {
+	let page = include_str!("fixtures/char_codes.html");
+	assert_eq!(solve(page).as_deref(), Some("D1N=abc123"));
+}
+
+#[test]
+fn joins_every_cookie_set() {
+	let page = include_str!("fixtures/two_cookies.html");
+	assert_eq!(solve(page).as_deref(), Some("vh_a=3; vh_b=xy"));
+}
+
+#[test]
+fn gives_up_on_unknown_variants() {
+	let page = include_str!("fixtures/unknown_variant.html");
+	assert!(is_challenge(page));
+	assert_eq!(solve(page), None);
+}
+
+#[test]
+fn ignores_real_pages() {
+	let page = include_str!("fixtures/page.html");
+	assert!(!is_challenge(page));
+	assert_eq!(solve(page), None);
+}
@@ -0,0 +1,271 @@
+// Backoff for JSON APIs that rate limit their clients.
+//
+// Nothing here waits: the wasm runtime has no way to sleep. A request turned
+// down with a 429, by the API or by the app's own limiter, or answered with
+// a 5xx fails with a `RateLimitError` right away, and the time it may be
+// retried at is remembered. Requests made before then fail without being
+// sent rather than adding to the limit. Only a request that got no response
+// at all is sent again within the same call.
+//
+// Sources call `RateLimit::install` from their `initialize` and use `json_rl`
+// in place of `json`.
+#![no_std]
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind},
+	std::{net::Request, ValueRef},
+};
+
+#[link(wasm_import_module = "net")]
+extern "C" {
+	#[link_name = "send"]
+	fn request_send(rd: i32);
+	#[link_name = "json"]
+	fn request_json(rd: i32) -> i32;
+	#[link_name = "close"]
+	fn request_close(rd: i32);
+	#[link_name = "get_data_size"]
+	fn request_get_data_size(rd: i32) -> i32;
+	#[link_name = "get_status_code"]
+	fn request_get_status_code(rd: i32) -> i32;
+
+	fn set_rate_limit(rate_limit: i32);
+	fn set_rate_limit_period(period: i32);
+}
+
+#[link(wasm_import_module = "std")]
+extern "C" {
+	fn destroy(rid: i32);
+	fn create_date(value: f64) -> i32;
+	fn read_date(ctx: i32) -> f64;
+}
+
+/// Headers holding the time a rate-limited request may be retried at, either
+/// as a unix timestamp or as a number of seconds.
+const RETRY_HEADERS: [&str; 2] = ["X-RateLimit-Retry-After", "Retry-After"];
+
+/// Retry-After values above this are unix timestamps rather than seconds.
+const TIMESTAMP_THRESHOLD: f64 = 1_000_000_000.0;
+
+/// How a source's requests are limited and retried.
+#[derive(Clone, Copy)]
+pub struct RateLimit {
+	/// Requests the app lets through per `period`.
+	pub requests: i32,
+	/// Length of the app's rate limit window, in seconds.
+	pub period: i32,
+	/// Extra attempts for a request that got no response at all.
+	pub max_retries: u32,
+	/// Wait after the first failure, in seconds, doubled with each failure
+	/// in a row.
+	pub base_delay: f64,
+	/// Longest wait backing off can lead to, in seconds.
+	pub max_delay: f64,
+}
+
+impl RateLimit {
+	pub const DEFAULT: RateLimit = RateLimit {
+		requests: 3,
+		period: 1,
+		max_retries: 2,
+		base_delay: 1.0,
+		max_delay: 60.0,
+	};
+
+	/// Sets the app's rate limit and the retry policy used by `SendRatelimited`.
+	pub fn install(self) {
+		unsafe {
+			set_rate_limit(self.requests);
+			set_rate_limit_period(self.period);
+			POLICY = self;
+		}
+	}
+}
+
+static mut POLICY: RateLimit = RateLimit::DEFAULT;
+// unix time before which requests fail without being sent
+static mut RETRY_AT: f64 = 0.0;
+// failures in a row, reset by the first successful request
+static mut FAILURES: u32 = 0;
+// why the last request failed, cleared by the next one that succeeds
+static mut LAST_ERROR: Option<RateLimitError> = None;
+
+/// Why a request failed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RateLimitError {
+	/// The API or the app's limiter turned the request down. It may be
+	/// retried from the given unix time on.
+	RateLimited { retry_at: f64 },
+	/// The API answered with a 5xx status. It may be retried from the given
+	/// unix time on.
+	ServerError { status: i32, retry_at: f64 },
+	/// No response came back, the request timed out or couldn't connect.
+	NoResponse,
+}
+
+/// Kind every `RateLimitError` becomes at the source boundary: the request
+/// gave no JSON to read. `last_error` tells the failures apart.
+pub const ERROR_KIND: AidokuErrorKind = AidokuErrorKind::JsonParseError;
+
+impl From<RateLimitError> for AidokuError {
+	fn from(_: RateLimitError) -> Self {
+		AidokuError { reason: ERROR_KIND }
+	}
+}
+
+/// Why the last request sent with `send_rl` failed, `None` once one succeeds.
+///
+/// An `AidokuError` of `ERROR_KIND` with a `last_error` came from this
+/// module rather than from reading a bad answer.
+pub fn last_error() -> Option<RateLimitError> {
+	unsafe { LAST_ERROR }
+}
+
+fn now() -> f64 {
+	unsafe {
+		let date = create_date(-1.0);
+		let result = read_date(date);
+		destroy(date);
+		result
+	}
+}
+
+/// Seconds to wait as told by a retry header value, if it can be read.
+pub fn parse_retry_after(value: &str, now: f64) -> Option<f64> {
+	let value = value.trim().parse::<f64>().ok()?;
+	if value > TIMESTAMP_THRESHOLD {
+		Some((value - now).max(0.0))
+	} else if value >= 0.0 {
+		Some(value)
+	} else {
+		None
+	}
+}
+
+/// Exponential backoff after `failures` failures in a row, spread over the
+/// upper half of the step by `jitter` (0 to 1) so clients don't retry in
+/// lockstep.
+pub fn backoff_delay(policy: &RateLimit, failures: u32, jitter: f64) -> f64 {
+	let step = policy.base_delay * (1u64 << failures.saturating_sub(1).min(16)) as f64;
+	let step = step.min(policy.max_delay);
+	step / 2.0 + step / 2.0 * jitter.clamp(0.0, 1.0)
+}
+
+/// A number from 0 to 1 taken from the clock's sub-millisecond digits.
+///
+/// The runtime has no random source, and those digits are unpredictable
+/// enough to spread retries.
+pub fn jitter(now: f64) -> f64 {
+	let scaled = now * 1000.0;
+	scaled - (scaled as u64) as f64
+}
+
+fn retry_after_header(request: &Request, now: f64) -> Option<f64> {
+	RETRY_HEADERS.iter().find_map(|header| {
+		request
+			.get_header(header)
+			.and_then(|value| parse_retry_after(&value.read(), now))
+	})
+}
+
+/// Whether an answer can be used, decided from its status, whether it has a
+/// body, its retry header and the failures in a row before it.
+///
+/// The app's limiter answers with a body-less 429, which is waited out for
+/// one `period`. A 429 from the API is waited out for the time in its retry
+/// header, and 5xx answers and 429s without one back off exponentially.
+pub fn check_answer(
+	policy: &RateLimit,
+	status: i32,
+	has_body: bool,
+	retry_after: Option<f64>,
+	now: f64,
+	failures: u32,
+) -> Result<(), RateLimitError> {
+	let backoff = || backoff_delay(policy, failures.saturating_add(1), jitter(now));
+	match status {
+		429 if !has_body => Err(RateLimitError::RateLimited {
+			retry_at: now + policy.period as f64,
+		}),
+		429 => Err(RateLimitError::RateLimited {
+			retry_at: now + retry_after.unwrap_or_else(backoff),
+		}),
+		500..=599 => Err(RateLimitError::ServerError {
+			status,
+			retry_at: now + backoff(),
+		}),
+		_ if status <= 0 => Err(RateLimitError::NoResponse),
+		_ => Ok(()),
+	}
+}
+
+/// Sends requests within a source's rate limit, see `check_answer`.
+///
+/// Requests sent before the time a failure may be retried at fail with the
+/// same error without being sent. A missing response is sent again, up to
+/// `max_retries` times.
+pub trait SendRatelimited {
+	fn send_rl(&self) -> Result<(), RateLimitError>;
+	fn json_rl(self) -> Result<ValueRef, RateLimitError>;
+}
+
+fn fail(error: RateLimitError) -> Result<(), RateLimitError> {
+	unsafe { LAST_ERROR = Some(error) };
+	Err(error)
+}
+
+impl SendRatelimited for Request {
+	fn send_rl(&self) -> Result<(), RateLimitError> {
+		let (retry_at, policy) = unsafe { (RETRY_AT, POLICY) };
+		if now() < retry_at {
+			return fail(RateLimitError::RateLimited { retry_at });
+		}
+
+		let mut attempts = 0;
+		loop {
+			unsafe { request_send(self.0) };
+			let status = unsafe { request_get_status_code(self.0) };
+			let has_body = unsafe { request_get_data_size(self.0) } >= 0;
+			let now = now();
+			// the app's limiter answers without headers
+			let retry_after = if has_body {
+				retry_after_header(self, now)
+			} else {
+				None
+			};
+			match check_answer(&policy, status, has_body, retry_after, now, unsafe {
+				FAILURES
+			}) {
+				Ok(()) => {
+					unsafe {
+						FAILURES = 0;
+						LAST_ERROR = None;
+					}
+					return Ok(());
+				}
+				Err(RateLimitError::NoResponse) if attempts < policy.max_retries => {
+					attempts += 1;
+				}
+				Err(error) => {
+					if let RateLimitError::RateLimited { retry_at }
+					| RateLimitError::ServerError { retry_at, .. } = error
+					{
+						unsafe {
+							FAILURES = FAILURES.saturating_add(1);
+							RETRY_AT = retry_at;
+						}
+					}
+					return fail(error);
+				}
+			}
+		}
+	}
+
+	/// Get the data as JSON
+	fn json_rl(self) -> Result<ValueRef, RateLimitError> {
+		let result = self
+			.send_rl()
+			.map(|_| ValueRef::new(unsafe { request_json(self.0) }));
+		unsafe { request_close(self.0) };
+		result
+	}
+}
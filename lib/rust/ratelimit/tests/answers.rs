@@ -0,0 +1,83 @@
+use ratelimit::{check_answer, RateLimit, RateLimitError};
+
+// no sub-millisecond digits, so backoffs take the lower end of their step
+const NOW: f64 = 1_700_000_000.0;
+
+#[test]
+fn uses_successful_answers() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(check_answer(&policy, 200, true, None, NOW, 3), Ok(()));
+	// a 404 is the source's to handle
+	assert_eq!(check_answer(&policy, 404, true, None, NOW, 0), Ok(()));
+}
+
+#[test]
+fn waits_out_the_apps_limiter_for_one_period() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(
+		check_answer(&policy, 429, false, None, NOW, 4),
+		Err(RateLimitError::RateLimited {
+			retry_at: NOW + policy.period as f64
+		})
+	);
+}
+
+#[test]
+fn waits_out_the_apis_retry_header() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(
+		check_answer(&policy, 429, true, Some(30.0), NOW, 0),
+		Err(RateLimitError::RateLimited {
+			retry_at: NOW + 30.0
+		})
+	);
+}
+
+#[test]
+fn backs_off_from_429s_without_a_retry_header() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(
+		check_answer(&policy, 429, true, None, NOW, 0),
+		Err(RateLimitError::RateLimited {
+			retry_at: NOW + 0.5
+		})
+	);
+	assert_eq!(
+		check_answer(&policy, 429, true, None, NOW, 2),
+		Err(RateLimitError::RateLimited {
+			retry_at: NOW + 2.0
+		})
+	);
+}
+
+#[test]
+fn backs_off_from_server_errors() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(
+		check_answer(&policy, 503, true, Some(30.0), NOW, 1),
+		Err(RateLimitError::ServerError {
+			status: 503,
+			retry_at: NOW + 1.0
+		})
+	);
+	assert_eq!(
+		check_answer(&policy, 500, false, None, NOW, 10),
+		Err(RateLimitError::ServerError {
+			status: 500,
+			retry_at: NOW + policy.max_delay / 2.0
+		})
+	);
+}
+
+#[test]
+fn reports_missing_responses() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(
+		check_answer(&policy, -1, false, None, NOW, 0),
+		Err(RateLimitError::NoResponse)
+	);
+	assert_eq!(
+		check_answer(&policy, 0, false, None, NOW, 0),
+		Err(RateLimitError::NoResponse)
+	);
+}
@@ -0,0 +1,64 @@
+use ratelimit::{backoff_delay, jitter, parse_retry_after, RateLimit};
+
+const NOW: f64 = 1_700_000_000.0;
+
+#[test]
+fn reads_retry_after_seconds() {
+	assert_eq!(parse_retry_after("12", NOW), Some(12.0));
+	assert_eq!(parse_retry_after(" 0.5 ", NOW), Some(0.5));
+}
+
+#[test]
+fn reads_retry_after_timestamps() {
+	assert_eq!(parse_retry_after("1700000030", NOW), Some(30.0));
+	// a time already passed needs no wait
+	assert_eq!(parse_retry_after("1699999990", NOW), Some(0.0));
+}
+
+#[test]
+fn ignores_unreadable_retry_after() {
+	assert_eq!(parse_retry_after("", NOW), None);
+	assert_eq!(parse_retry_after("-3", NOW), None);
+	// http dates aren't sent by the apis this is used with
+	assert_eq!(
+		parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", NOW),
+		None
+	);
+}
+
+#[test]
+fn doubles_the_backoff_with_each_failure() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(backoff_delay(&policy, 1, 1.0), 1.0);
+	assert_eq!(backoff_delay(&policy, 2, 1.0), 2.0);
+	assert_eq!(backoff_delay(&policy, 3, 1.0), 4.0);
+	// no failures yet waits as long as the first one
+	assert_eq!(backoff_delay(&policy, 0, 1.0), 1.0);
+}
+
+#[test]
+fn spreads_the_backoff_over_the_upper_half() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(backoff_delay(&policy, 3, 0.0), 2.0);
+	assert_eq!(backoff_delay(&policy, 3, 0.5), 3.0);
+	// out of range jitter is clamped
+	assert_eq!(backoff_delay(&policy, 3, 7.0), 4.0);
+	assert_eq!(backoff_delay(&policy, 3, -1.0), 2.0);
+}
+
+#[test]
+fn caps_the_backoff() {
+	let policy = RateLimit::DEFAULT;
+	assert_eq!(backoff_delay(&policy, 7, 1.0), policy.max_delay);
+	assert_eq!(backoff_delay(&policy, u32::MAX, 1.0), policy.max_delay);
+}
+
+#[test]
+fn takes_jitter_from_the_sub_millisecond_digits() {
+	let value = jitter(NOW + 0.0125);
+	assert!((value - 0.5).abs() < 1e-3);
+	for now in [NOW, NOW + 0.1234567, NOW + 0.9999] {
+		let value = jitter(now);
+		assert!((0.0..1.0).contains(&value));
+	}
+}
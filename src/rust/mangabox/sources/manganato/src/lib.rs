@@ -1,54 +1,50 @@
 #![no_std]
 use aidoku::{
-	prelude::*, error::Result, std::String, std::Vec, std::net::Request,
-	Filter, Listing, Manga, MangaPageResult, Page, Chapter, DeepLink,
+	error::Result, prelude::*, std::net::Request, std::String, std::Vec, Chapter, DeepLink, Filter,
+	Listing, Manga, MangaPageResult, Page,
 };
-
-// use mangabox_template::helper::*;
-use mangabox_template::template;
+use mangabox_template::template::MangaBoxSource;
+
+fn get_instance() -> MangaBoxSource {
+	MangaBoxSource {
+		base_url: "https://manganato.com",
+		image_referer: "https://readmanganato.com",
+		chapter_domains: &["chapmanganato.com", "readmanganato.com"],
+		..Default::default()
+	}
+}
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	template::get_manga_list(
-		String::from("https://manganato.com"), 
-		String::from("div.content-genres-item"),
-		filters, page
-	)
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
 fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
-	template::get_manga_listing(
-		String::from("https://manganato.com"), 
-		String::from("div.content-genres-item"),
-		listing, page
-	)
+	get_instance().get_manga_listing(listing, page)
 }
 
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
-	template::get_manga_details(id)
+	get_instance().get_manga_details(id)
 }
 
 #[get_chapter_list]
 fn get_chapter_list(id: String) -> Result<Vec<Chapter>> {
-	template::get_chapter_list(id, String::from("MMM dd,yyyy HH:mm"))
+	get_instance().get_chapter_list(id)
 }
 
 #[get_page_list]
 fn get_page_list(id: String) -> Result<Vec<Page>> {
-	template::get_page_list(id)
+	get_instance().get_page_list(id)
 }
 
-#[modify_image_request] 
+#[modify_image_request]
 fn modify_image_request(request: Request) {
-	template::modify_image_request(
-		String::from("https://readmanganato.com"),
-		request
-	)
+	get_instance().modify_image_request(request)
 }
 
 #[handle_url]
 pub fn handle_url(url: String) -> Result<DeepLink> {
-	template::handle_url(url)
-}
\ No newline at end of file
+	get_instance().handle_url(url)
+}
@@ -1,184 +1,457 @@
 use aidoku::{
-	error::Result, std::String, std::ObjectRef, std::Vec, std::net::Request, std::net::HttpMethod,
-	Filter, FilterType, Listing, Manga, MangaPageResult, Page, MangaStatus, MangaContentRating, MangaViewer, Chapter, DeepLink,
+	error::{AidokuError, AidokuErrorKind, Result},
+	prelude::format,
+	std::{defaults::defaults_get, net::HttpMethod, net::Request, ObjectRef, String, Vec},
+	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaContentRating, MangaPageResult,
+	MangaStatus, MangaViewer, Page,
 };
 
+use common::image::image_available;
+
 use crate::helper::*;
 
-pub fn get_manga_list(base_url: String, selector: String, filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut included_tags: Vec<String> = Vec::new();
-	let mut excluded_tags: Vec<String> = Vec::new();
-	let mut sort: String = String::new();
-	let mut title: String = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = filter.value.as_string()?.read();
-			},
-			FilterType::Genre => {
-				match filter.value.as_int().unwrap_or(-1) {
-					0 => excluded_tags.push(get_tag_id(String::from(&filter.name))),
-					1 => included_tags.push(get_tag_id(String::from(&filter.name))),
-					_ => continue,
+// a select filter from filters.json sent as an advanced search parameter
+pub struct SearchSelect {
+	pub name: &'static str,
+	pub param: &'static str,
+	// value sent for each option index, an empty string leaves the parameter out
+	pub values: &'static [&'static str],
+}
+
+pub struct MangaBoxSource {
+	pub base_url: &'static str,
+	pub lang: &'static str,
+	// sent as the referer of image requests, the host chapters are read on
+	pub image_referer: &'static str,
+	// other hosts serving the chapter reader, e.g. chapmanganato.com, chapter
+	// ids always use the first one so links from any of them match
+	pub chapter_domains: &'static [&'static str],
+	// values of the `content_server` cookie picking the image host, the
+	// `imageServer` setting selects which one is tried first
+	pub content_servers: &'static [&'static str],
+
+	// listing names, as declared in source.json
+	pub listing_latest: &'static str,
+	pub listing_new: &'static str,
+	pub listing_top: &'static str,
+
+	pub manga_cell: &'static str,
+	pub manga_cell_anchor: &'static str,
+	pub manga_cell_image: &'static str,
+	// "LAST(42)" link of the pager, the other page links are used without it
+	pub last_page: &'static str,
+	pub pager: &'static str,
+
+	pub search_selects: &'static [SearchSelect],
+
+	pub manga_details: &'static str,
+	pub manga_details_title: &'static str,
+	pub manga_details_cover: &'static str,
+	pub manga_details_author: &'static str,
+	pub manga_details_description: &'static str,
+	pub manga_details_tags: &'static str,
+	pub manga_details_status: &'static str,
+
+	pub chapter: &'static str,
+	pub chapter_date: &'static str,
+	pub chapter_date_format: &'static str,
+
+	pub page_image: &'static str,
+
+	pub status_mapping: fn(String) -> MangaStatus,
+	pub nsfw_genres: &'static [&'static str],
+	pub scroll_genres: &'static [&'static str],
+}
+
+impl Default for MangaBoxSource {
+	fn default() -> MangaBoxSource {
+		MangaBoxSource {
+			base_url: "",
+			lang: "en",
+			image_referer: "",
+			chapter_domains: &[],
+			content_servers: &["server1", "server2"],
+
+			listing_latest: "Latest Updates",
+			listing_new: "New Manga",
+			listing_top: "Top Manga",
+
+			manga_cell: "div.content-genres-item",
+			manga_cell_anchor: "a",
+			manga_cell_image: "img",
+			last_page: "a.page-last",
+			pager: "div.group-page a, div.panel-page-number a",
+
+			search_selects: &[
+				SearchSelect {
+					name: "Status",
+					param: "sts",
+					values: &["", "ongoing", "completed"],
+				},
+				SearchSelect {
+					name: "Search by",
+					param: "keyt",
+					values: &["", "title", "alternative", "author"],
+				},
+			],
+
+			manga_details: "div.panel-story-info",
+			manga_details_title: "h1",
+			manga_details_cover: "span.info-image > img",
+			manga_details_author: "td:contains(Author) + td a",
+			manga_details_description: "div.panel-story-info-description",
+			manga_details_tags: "td:contains(Genre) + td a",
+			manga_details_status: "td:contains(Status) + td",
+
+			chapter: "div.panel-story-chapter-list > ul.row-content-chapter > li",
+			chapter_date: "span.chapter-time",
+			chapter_date_format: "MMM dd,yyyy HH:mm",
+
+			page_image: "div.container-chapter-reader > img",
+
+			status_mapping: status_from_string,
+			nsfw_genres: &["Smut", "Mature", "Ecchi", "Adult"],
+			scroll_genres: &["Webtoons"],
+		}
+	}
+}
+
+impl MangaBoxSource {
+	pub fn get_manga_list(&self, filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
+		let mut included_tags: Vec<String> = Vec::new();
+		let mut excluded_tags: Vec<String> = Vec::new();
+		let mut sort: String = String::new();
+		let mut title: String = String::new();
+		let mut params: Vec<String> = Vec::new();
+		for filter in filters {
+			match filter.kind {
+				FilterType::Title => {
+					title = filter.value.as_string()?.read();
 				}
-			},
-			FilterType::Sort => {
-				let value = match filter.value.as_object() {
-					Ok(value) => value,
-					Err(_) => continue,
-				};
-				let index = value.get("index").as_int().unwrap_or(0);
-				let option = match index {
-					0 => "",
-					1 => "newest",
-					2 => "topview",
-					3 => "az",
-					_ => continue,
-				};
-				sort = String::from(option)
-			},
-			_ => continue,
+				FilterType::Genre => {
+					let id = match filter.object.get("id").as_string() {
+						Ok(id) => id.read(),
+						Err(_) => continue,
+					};
+					match filter.value.as_int().unwrap_or(-1) {
+						0 => excluded_tags.push(id),
+						1 => included_tags.push(id),
+						_ => continue,
+					}
+				}
+				FilterType::Select => {
+					if let Some(select) = self.search_selects.iter().find(|s| s.name == filter.name)
+					{
+						let index = filter.value.as_int().unwrap_or(-1);
+						if index < 0 {
+							continue;
+						}
+						match select.values.get(index as usize) {
+							Some(value) if !value.is_empty() => {
+								params.push(format!("{}={}", select.param, value))
+							}
+							_ => continue,
+						}
+					}
+				}
+				FilterType::Sort => {
+					let value = match filter.value.as_object() {
+						Ok(value) => value,
+						Err(_) => continue,
+					};
+					let index = value.get("index").as_int().unwrap_or(0);
+					let option = match index {
+						0 => "",
+						1 => "newest",
+						2 => "topview",
+						3 => "az",
+						_ => continue,
+					};
+					sort = String::from(option)
+				}
+				_ => continue,
+			}
 		}
+
+		let url = get_search_url(
+			String::from(self.base_url),
+			title,
+			page,
+			included_tags,
+			excluded_tags,
+			sort,
+			params,
+		);
+		let html = Request::new(url.as_str(), HttpMethod::Get).html();
+		let mut mangas: Vec<Manga> = Vec::new();
+		for item in html.select(self.manga_cell).array() {
+			let item_node = item.as_node();
+			let anchor = item_node.select(self.manga_cell_anchor).first();
+			let title = anchor.attr("title").read();
+			let id = anchor.attr("href").read();
+			let cover = item_node
+				.select(self.manga_cell_image)
+				.first()
+				.attr("src")
+				.read();
+			mangas.push(Manga {
+				id: id.clone(),
+				cover,
+				title,
+				author: String::new(),
+				artist: String::new(),
+				description: String::new(),
+				url: id,
+				categories: Vec::new(),
+				status: MangaStatus::Unknown,
+				nsfw: MangaContentRating::Safe,
+				viewer: MangaViewer::Default,
+			});
+		}
+		let last_page = match parse_page_number(html.select(self.last_page).text().read()) {
+			Some(last_page) => last_page,
+			None => html
+				.select(self.pager)
+				.array()
+				.filter_map(|link| parse_page_number(link.as_node().text().read()))
+				.max()
+				.unwrap_or(1),
+		};
+		Ok(MangaPageResult {
+			manga: mangas,
+			has_more: page < last_page,
+		})
 	}
 
-	let mut mangas: Vec<Manga> = Vec::new();
-	let url = get_search_url(base_url, title, page, included_tags, excluded_tags, sort);
-	let html = Request::new(url.as_str(), HttpMethod::Get).html();
-	for item in html.select(&selector).array() {
-		let item_node = item.as_node();
-		let title = item_node.select("a").first().attr("title").read();
-		let id = item_node.select("a").first().attr("href").read();
-		let cover = item_node.select("img").first().attr("src").read();
-		mangas.push(Manga {
-			id,
+	pub fn get_manga_listing(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+		let mut filters: Vec<Filter> = Vec::new();
+		let mut selection = ObjectRef::new();
+
+		let index: i32 = match listing.name.as_str() {
+			name if name == self.listing_latest => 0,
+			name if name == self.listing_new => 1,
+			name if name == self.listing_top => 2,
+			_ => {
+				return Err(AidokuError {
+					reason: AidokuErrorKind::Unimplemented,
+				})
+			}
+		};
+		selection.set("ascending", false.into());
+		selection.set("index", index.into());
+		filters.push(Filter {
+			kind: FilterType::Sort,
+			name: String::from("Sort"),
+			value: selection.0,
+		});
+
+		self.get_manga_list(filters, page)
+	}
+
+	pub fn get_manga_details(&self, id: String) -> Result<Manga> {
+		let html = Request::new(id.clone().as_str(), HttpMethod::Get).html();
+		let details = html.select(self.manga_details);
+		let title = details.select(self.manga_details_title).text().read();
+		let cover = details.select(self.manga_details_cover).attr("src").read();
+		let author = join_string_array(
+			details.select(self.manga_details_author).array(),
+			String::from(", "),
+		);
+		let description = details.select(self.manga_details_description).text().read();
+		let mut categories = Vec::new();
+		let mut nsfw = MangaContentRating::Safe;
+		let mut viewer = MangaViewer::Default;
+		for node in details.select(self.manga_details_tags).array() {
+			let category = node.as_node().text().read();
+			if self.nsfw_genres.contains(&category.as_str()) {
+				nsfw = MangaContentRating::Nsfw;
+			}
+			if self.scroll_genres.contains(&category.as_str()) {
+				viewer = MangaViewer::Scroll;
+			}
+			categories.push(category);
+		}
+		let status = (self.status_mapping)(details.select(self.manga_details_status).text().read());
+		Ok(Manga {
+			id: id.clone(),
 			cover,
 			title,
-			author: String::new(),
+			author,
 			artist: String::new(),
-			description: String::new(),
-			url: String::new(),
-			categories: Vec::new(),
-			status: MangaStatus::Unknown,
-			nsfw: MangaContentRating::Safe,
-			viewer: MangaViewer::Default
-		});
+			description,
+			url: id,
+			categories,
+			status,
+			nsfw,
+			viewer,
+		})
 	}
-	let last_page_string = html.select("a.page-last").text().read();
-	let mut last_page = 1;
-	if last_page_string.len() > 0 {
-		last_page = String::from(&last_page_string[5..last_page_string.len()-1]).parse::<i32>().unwrap_or(1);
+
+	pub fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>> {
+		let mut chapters: Vec<Chapter> = Vec::new();
+		let html = Request::new(id.as_str(), HttpMethod::Get).html();
+		for chapter in html.select(self.chapter).array() {
+			let chapter_node = chapter.as_node();
+			let title = chapter_node.select("a").text().read();
+			let chapter_url = chapter_node.select("a").attr("href").read();
+			let chapter_id = self.chapter_id(&chapter_url);
+			let chapter_number = get_chapter_number(chapter_id.clone());
+			let date_updated = chapter_node
+				.select(self.chapter_date)
+				.attr("title")
+				.0
+				.as_date(self.chapter_date_format)
+				.unwrap_or(0.0);
+			chapters.push(Chapter {
+				id: chapter_id.clone(),
+				title,
+				volume: -1.0,
+				chapter: chapter_number,
+				date_updated,
+				scanlator: String::new(),
+				url: chapter_url,
+				lang: String::from(self.lang),
+			});
+		}
+		Ok(chapters)
 	}
-	Ok(MangaPageResult {
-		manga: mangas,
-		has_more: page < last_page,
-	})
-}
 
-pub fn get_manga_listing(base_url: String, selector: String, listing: Listing, page: i32) -> Result<MangaPageResult> {
-	let mut filters: Vec<Filter> = Vec::new();
-	let mut selection = ObjectRef::new();
-
-	selection.set("ascending", false.into());
-	selection.set("index", match listing.name.as_str() {
-		"Latest Updates" => 0i32.into(),
-		"New Manga" => 1i32.into(),
-		"Top Manga" => 2i32.into(),
-		&_ => 0i32.into()
-	});
-	filters.push(Filter {
-		kind: FilterType::Sort,
-		name: String::from("Sort"),
-		value: selection.0
-	});
-
-	get_manga_list(base_url, selector, filters, page)
-}
+	fn is_known_host(&self, host: &str) -> bool {
+		self.base_url.split('/').nth(2) == Some(host) || self.chapter_domains.contains(&host)
+	}
 
-pub fn get_manga_details(id: String) -> Result<Manga> {
-	let html = Request::new(id.clone().as_str(), HttpMethod::Get).html();
-	let details = html.select("div.panel-story-info");
-	let title = details.select("h1").text().read();
-	let cover = details.select("span.info-image > img").attr("src").read();
-	let author = join_string_array(details.select("td:contains(Author) + td a").array(), String::from(", "));
-	let description = details.select("div.panel-story-info-description").text().read();
-	let mut categories = Vec::new();
-	let mut nsfw = MangaContentRating::Safe;
-	let mut viewer = MangaViewer::Default;
-	for node in details.select("td:contains(Genre) + td a").array() {
-		let category = node.as_node().text().read();
-		if category.clone().as_str() == "Smut" || category.clone().as_str() == "Mature" || category.clone().as_str() == "Ecchi" || category.clone().as_str() == "Adult" {
-			nsfw = MangaContentRating::Nsfw;
+	// the same chapter is linked from several reader hosts
+	fn chapter_id(&self, url: &str) -> String {
+		let host = url.split('/').nth(2).unwrap_or_default();
+		match self.chapter_domains.first() {
+			Some(canonical) if self.is_known_host(host) && host != *canonical => {
+				url.replacen(host, canonical, 1)
+			}
+			_ => String::from(url),
 		}
-		if category.clone().as_str() == "Webtoons" {
-			viewer = MangaViewer::Scroll;
+	}
+
+	fn image_referer(&self) -> &'static str {
+		if self.image_referer.is_empty() {
+			self.base_url
+		} else {
+			self.image_referer
 		}
-		categories.push(category.clone());
 	}
-	let status = status_from_string(details.select("td:contains(Status) + td").text().read());
-	Ok(Manga {
-		id: id.clone(),
-		cover,
-		title,
-		author,
-		artist: String::new(),
-		description,
-		url: id.clone(),
-		categories,
-		status,
-		nsfw,
-		viewer
-	})
-}
 
-pub fn get_chapter_list(id: String, date_format: String) -> Result<Vec<Chapter>> {
-	let mut chapters: Vec<Chapter> = Vec::new();
-	let html = Request::new(id.clone().as_str(), HttpMethod::Get).html();
-	for chapter in html.select("div.panel-story-chapter-list > ul.row-content-chapter > li").array() {
-		let chapter_node = chapter.as_node();
-		let title = chapter_node.select("a").text().read();
-		let chapter_id = chapter_node.select("a").attr("href").read();
-		let chapter_url = chapter_node.select("a").attr("href").read();
-		let chapter_number = get_chapter_number(chapter_id.clone());
-		let date_updated = chapter_node.select("span.chapter-time").attr("title").0.as_date(date_format.as_str()).unwrap_or(0.0);
-		chapters.push(Chapter {
-			id: chapter_id,
-			title,
-			volume: -1.0,
-			chapter: chapter_number,
-			date_updated,
-			scanlator: String::new(),
-			url: chapter_url,
-			lang: String::from("en"),
-		});
+	// content servers, the one chosen in settings first
+	fn server_order(&self) -> Vec<&'static str> {
+		let preferred = defaults_get("imageServer")
+			.as_string()
+			.map(|value| value.read())
+			.unwrap_or_default();
+		let mut servers: Vec<&'static str> = Vec::with_capacity(self.content_servers.len());
+		for server in self.content_servers {
+			if *server == preferred {
+				servers.insert(0, *server);
+			} else {
+				servers.push(*server);
+			}
+		}
+		servers
 	}
-	Ok(chapters)
-}
 
-pub fn get_page_list(id: String) -> Result<Vec<Page>> {
-	let mut pages: Vec<Page> = Vec::new();
-	let html = Request::new(id.clone().as_str(), HttpMethod::Get).html();
-	let mut at = 0;
-	for page in html.select("div.container-chapter-reader > img").array() {
-		let page_node = page.as_node();
-		let page_url = page_node.attr("src").read();
-		pages.push(Page {
-			index: at,
-			url: page_url,
-			base64: String::new(),
-			text: String::new(),
-		});
-		at += 1;
+	fn get_page_urls(&self, id: &str, server: &str) -> Vec<String> {
+		let request = Request::new(id, HttpMethod::Get);
+		let request = if server.is_empty() {
+			request
+		} else {
+			request.header("Cookie", format!("content_server={}", server).as_str())
+		};
+		request
+			.html()
+			.select(self.page_image)
+			.array()
+			.map(|page| page.as_node().attr("src").read())
+			.filter(|url| !url.is_empty())
+			.collect::<Vec<_>>()
 	}
-	Ok(pages)
-}
 
-pub fn modify_image_request(base_url: String, request: Request) {
-	request.header("Referer", &base_url);
-}
+	pub fn get_page_list(&self, id: String) -> Result<Vec<Page>> {
+		let mut servers = self.server_order();
+		if servers.is_empty() {
+			servers.push("");
+		}
+		// the preferred server may have lost a chapter's images, use the first
+		// one whose images load and the preferred one if none of them do
+		let mut fallback: Option<Vec<String>> = None;
+		let mut urls: Vec<String> = Vec::new();
+		for server in servers.iter() {
+			let candidate = self.get_page_urls(&id, server);
+			let available = match candidate.first() {
+				Some(url) => {
+					servers.len() < 2 || image_available(url, &[("Referer", self.image_referer())])
+				}
+				None => false,
+			};
+			if available {
+				urls = candidate;
+				break;
+			}
+			if fallback.is_none() && !candidate.is_empty() {
+				fallback = Some(candidate);
+			}
+		}
+		if urls.is_empty() {
+			urls = fallback.unwrap_or_default();
+		}
+		Ok(urls
+			.into_iter()
+			.enumerate()
+			.map(|(at, url)| Page {
+				index: at as i32,
+				url,
+				base64: String::new(),
+				text: String::new(),
+			})
+			.collect::<Vec<_>>())
+	}
+
+	pub fn modify_image_request(&self, request: Request) {
+		request.header("Referer", self.image_referer());
+	}
 
-pub fn handle_url(url: String) -> Result<DeepLink> {
-	Ok(DeepLink {
-		manga: Some(get_manga_details(url.clone())?),
-		chapter: None
-	})
+	pub fn handle_url(&self, url: String) -> Result<DeepLink> {
+		// https://chapmanganato.com/manga-aa951409/chapter-1
+		// https://readmangabat.com/read-sq395925-chap-1
+		let known_host = self.is_known_host(url.split('/').nth(2).unwrap_or_default());
+		let path = url.trim_end_matches('/');
+		let last_segment = path.rsplit('/').next().unwrap_or_default();
+		let manga_url = if !known_host {
+			None
+		} else if last_segment.starts_with("chapter-") {
+			Some(String::from(&path[..path.len() - last_segment.len() - 1]))
+		} else {
+			last_segment
+				.find("-chap-")
+				.map(|at| String::from(&path[..path.len() - last_segment.len() + at]))
+		};
+		match manga_url {
+			Some(manga_url) => {
+				let chapter = Chapter {
+					id: self.chapter_id(path),
+					title: String::new(),
+					volume: -1.0,
+					chapter: get_chapter_number(String::from(path)),
+					date_updated: -1.0,
+					scanlator: String::new(),
+					url: String::from(path),
+					lang: String::from(self.lang),
+				};
+				Ok(DeepLink {
+					manga: Some(self.get_manga_details(manga_url)?),
+					chapter: Some(chapter),
+				})
+			}
+			None => Ok(DeepLink {
+				manga: Some(self.get_manga_details(url)?),
+				chapter: None,
+			}),
+		}
+	}
 }
@@ -16,7 +16,7 @@ fn get_instance() -> MangaStreamSource {
 		//next_page_2: ".pagination .next",
 		//manga_details_author: "tr:contains(Author) td:eq(1)",
 		//manga_details_categories: ".seriestugenre a",
-		manga_title_trim: get_title_skip(),
+		manga_title_skip: get_title_skip(),
 		..Default::default()
 	}
 }
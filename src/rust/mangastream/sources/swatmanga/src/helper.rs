@@ -13,14 +13,14 @@ use mangastream_template::{
 // parse manga details page
 pub fn parse_manga_details(source: &MangaStreamSource, id: String) -> Result<Manga> {
 	let html = Request::new(id.as_str(), HttpMethod::Get).html();
-	let title = html.select(source.manga_details_title).text().read();
+	let raw_title = html.select(source.manga_details_title).text().read();
 	let image = html
 		.select(source.manga_details_cover)
 		.attr("src")
 		.read()
 		.replace("?resize=165,225", "");
 	let cover: String = if image.starts_with("data:") || image.is_empty() {
-		let title_id = title.replace(' ', "+").replace('’', "%27");
+		let title_id = raw_title.replace(' ', "+").replace('’', "%27");
 		let url = format!("{}/?s={}", source.base_url, title_id);
 		println!("{} -> {}", title_id, url);
 		Request::new(&url, HttpMethod::Get)
@@ -33,6 +33,7 @@ pub fn parse_manga_details(source: &MangaStreamSource, id: String) -> Result<Man
 	};
 
 	println!("{}", cover);
+	let title = source.normalize_title(&raw_title);
 	let mut author = String::from(
 		html.select(source.manga_details_author)
 			.text()
@@ -230,6 +230,128 @@ pub fn get_image_src(node: Node) -> String {
 	append_protocol(encoded_img)
 }
 
+// find the byte range of the first case-insensitive occurrence of `needle`
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+	if needle.is_empty() {
+		return None;
+	}
+	for (start, _) in haystack.char_indices() {
+		let mut rest = haystack[start..].char_indices();
+		let mut end = start;
+		let matched = needle.chars().all(|n| match rest.next() {
+			Some((at, h)) if h == n || h.to_lowercase().eq(n.to_lowercase()) => {
+				end = start + at + h.len_utf8();
+				true
+			}
+			_ => false,
+		});
+		if matched {
+			return Some((start, end));
+		}
+	}
+	None
+}
+
+pub fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+	find_ignore_case(haystack, needle).is_some()
+}
+
+/// Patterns every source trims from its titles, on top of its own
+/// `manga_title_trim`.
+pub const DEFAULT_TITLE_TRIM: [&str; 3] = ["(Manhwa)", "(Manhua)", "Light Novel"];
+
+const TITLE_SEPARATORS: [char; 7] = ['-', '–', '—', '|', ':', ',', '~'];
+
+fn is_separator(c: char) -> bool {
+	c.is_whitespace() || TITLE_SEPARATORS.contains(&c)
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+	a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+// a pattern only matches whole words, "English" doesn't match "Englishman"
+fn splits_word(pattern_edge: Option<char>, next: Option<char>) -> bool {
+	pattern_edge.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric)
+}
+
+// the rest of the title if it starts with `pattern`, ignoring case
+fn strip_prefix_ignore_case<'a>(title: &'a str, pattern: &str) -> Option<&'a str> {
+	let mut chars = title.chars();
+	for p in pattern.chars() {
+		if !eq_ignore_case(chars.next()?, p) {
+			return None;
+		}
+	}
+	let rest = chars.as_str();
+	if splits_word(pattern.chars().next_back(), rest.chars().next()) {
+		return None;
+	}
+	Some(rest)
+}
+
+// the start of the title if it ends with `pattern`, ignoring case
+fn strip_suffix_ignore_case<'a>(title: &'a str, pattern: &str) -> Option<&'a str> {
+	let mut chars = title.chars();
+	for p in pattern.chars().rev() {
+		if !eq_ignore_case(chars.next_back()?, p) {
+			return None;
+		}
+	}
+	let rest = chars.as_str();
+	if splits_word(pattern.chars().next(), rest.chars().next_back()) {
+		return None;
+	}
+	Some(rest)
+}
+
+// strip a leading or trailing [scan tag] from the title, if any
+fn strip_bracket_tag(title: &str) -> Option<&str> {
+	if title.starts_with('[') {
+		title
+			.find(']')
+			.map(|end| title[end + 1..].trim_start_matches(is_separator))
+	} else if title.ends_with(']') {
+		title
+			.rfind('[')
+			.map(|start| title[..start].trim_end_matches(is_separator))
+	} else {
+		None
+	}
+}
+
+// clean up a title by removing the default and source trim patterns
+// (case-insensitive), bracketed scan tags and the separators they leave
+// behind, only ever from the start or the end of the title
+pub fn normalize_title(title: &str, trim: &[String], strip_tags: bool) -> String {
+	let patterns = DEFAULT_TITLE_TRIM
+		.iter()
+		.copied()
+		.chain(trim.iter().map(String::as_str))
+		.filter(|pattern| !pattern.trim().is_empty());
+	let mut normalized = title.trim();
+	loop {
+		let stripped = patterns
+			.clone()
+			.find_map(|pattern| {
+				strip_prefix_ignore_case(normalized, pattern)
+					.map(|rest| rest.trim_start_matches(is_separator))
+			})
+			.or_else(|| {
+				patterns.clone().find_map(|pattern| {
+					strip_suffix_ignore_case(normalized, pattern)
+						.map(|rest| rest.trim_end_matches(is_separator))
+				})
+			})
+			.or_else(|| strip_tags.then(|| strip_bracket_tag(normalized)).flatten());
+		match stripped {
+			Some(rest) if !rest.is_empty() => normalized = rest,
+			_ => break,
+		}
+	}
+	String::from(normalized)
+}
+
 pub fn append_protocol(url: String) -> String {
 	if url.starts_with("https") || url.starts_with("http") {
 		url
@@ -256,3 +378,79 @@ pub fn urlencode<T: AsRef<[u8]>>(url: T) -> String {
     }
     String::from_utf8(result).unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn normalize(title: &str, trim: &[&str]) -> String {
+		let trim: Vec<String> = trim.iter().map(|pattern| String::from(*pattern)).collect();
+		normalize_title(title, &trim, true)
+	}
+
+	#[test]
+	fn trims_default_patterns() {
+		assert_eq!(normalize("Solo Leveling (Manhwa)", &[]), "Solo Leveling");
+		assert_eq!(
+			normalize("Tales of Demons and Gods (manhua)", &[]),
+			"Tales of Demons and Gods"
+		);
+		assert_eq!(normalize("Overlord – Light Novel", &[]), "Overlord");
+		assert_eq!(normalize("Light Novel", &[]), "Light Novel");
+	}
+
+	#[test]
+	fn keeps_defaults_with_source_patterns() {
+		// kiryuu, mangkomik and westmanga
+		let trim = ["Bahasa Indonesia"];
+		assert_eq!(normalize("One Piece Bahasa Indonesia", &trim), "One Piece");
+		assert_eq!(
+			normalize("Nano Machine (Manhwa) Bahasa Indonesia", &trim),
+			"Nano Machine"
+		);
+		// komikucom and manhwaindo
+		assert_eq!(normalize("Komik Eleceed", &["Komik "]), "Eleceed");
+		// kumascans
+		assert_eq!(
+			normalize("The Greatest Estate Developer English", &["English"]),
+			"The Greatest Estate Developer"
+		);
+	}
+
+	#[test]
+	fn only_trims_the_ends() {
+		// manhwaland
+		assert_eq!(
+			normalize("Manhwa Solo Leveling", &["Manhwa "]),
+			"Solo Leveling"
+		);
+		assert_eq!(
+			normalize("Reincarnated as a Manhwa Villain", &["Manhwa "]),
+			"Reincarnated as a Manhwa Villain"
+		);
+		assert_eq!(
+			normalize("The Englishman's Bride", &["English"]),
+			"The Englishman's Bride"
+		);
+		assert_eq!(
+			normalize("Re:Zero -Starting Life in Another World-", &[]),
+			"Re:Zero -Starting Life in Another World-"
+		);
+	}
+
+	#[test]
+	fn strips_scan_tags() {
+		assert_eq!(
+			normalize("[Flame] The Beginning After the End", &[]),
+			"The Beginning After the End"
+		);
+		assert_eq!(
+			normalize("Omniscient Reader - [Asura]", &[]),
+			"Omniscient Reader"
+		);
+		assert_eq!(
+			normalize_title("Omniscient Reader [Asura]", &[], false),
+			"Omniscient Reader [Asura]"
+		);
+	}
+}
@@ -17,6 +17,8 @@ pub struct MangaStreamSource {
 	pub manga_selector: &'static str,
 	pub manga_title: &'static str,
 	pub manga_title_trim: Vec<String>,
+	pub manga_title_skip: Vec<String>,
+	pub manga_title_strip_tags: bool,
 	pub last_page_text: &'static str,
 	pub last_page_text_2: &'static str,
 	pub status_options: [&'static str; 5],
@@ -62,7 +64,9 @@ impl Default for MangaStreamSource {
 			next_page_2: ".hpage a.r",
 			manga_selector: ".listupd .bsx",
 			manga_title: "a",
-			manga_title_trim: ["light novel".into()].to_vec(),
+			manga_title_trim: Vec::new(),
+			manga_title_skip: ["light novel".into()].to_vec(),
+			manga_title_strip_tags: true,
 			last_page_text: "Next",
 			last_page_text_2: "NNNN",
 			status_options: [ "Ongoing", "Completed", "Hiatus", "Cancelled", "Dropped" ],
@@ -100,6 +104,11 @@ impl Default for MangaStreamSource {
 }
 
 impl MangaStreamSource {
+	// apply the source's title cleanup rules
+	pub fn normalize_title(&self, title: &str) -> String {
+		normalize_title(title, &self.manga_title_trim, self.manga_title_strip_tags)
+	}
+
 	// parse the homepage and filters
 	pub fn parse_manga_list(&self, filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 		let mut included_tags: Vec<String> = Vec::new();
@@ -171,14 +180,15 @@ impl MangaStreamSource {
 		let html = Request::new(&url, HttpMethod::Get).html();
 		for manga in html.select(self.manga_selector).array() {
 			let manga_node = manga.as_node();
-			let title = manga_node.select(self.manga_title).attr("title").read();
+			let raw_title = manga_node.select(self.manga_title).attr("title").read();
 			if self
-				.manga_title_trim
+				.manga_title_skip
 				.iter()
-				.any(|i| title.to_lowercase().contains(i))
+				.any(|i| contains_ignore_case(&raw_title, i))
 			{
 				continue;
 			}
+			let title = self.normalize_title(&raw_title);
 			let id = manga_node.select("a").attr("href").read();
 			let cover = get_image_src(manga_node);
 			mangas.push(Manga {
@@ -211,15 +221,7 @@ impl MangaStreamSource {
 	// parse manga details page
 	pub fn parse_manga_details(&self, id: String) -> Result<Manga> {
 		let html = Request::new(id.as_str(), HttpMethod::Get).html();
-		let raw_title = html.select(self.manga_details_title).text().read();
-		let mut title = String::new();
-		for i in self.manga_title_trim.iter() {
-			if raw_title.clone().contains(i) {
-				title = raw_title.replace(i, "");
-			} else {
-				title = raw_title.clone();
-			}
-		}
+		let title = self.normalize_title(&html.select(self.manga_details_title).text().read());
 		let cover: String = html
 			.select(self.manga_details_cover)
 			.first()
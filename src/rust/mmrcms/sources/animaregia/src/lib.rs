@@ -3,16 +3,23 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaContentRating, MangaPageResult, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaContentRating, MangaPageResult, Page,
 };
 use lazy_static::lazy_static;
-use mmrcms_template::template::{cache_manga_page, MMRCMSSource, CACHED_MANGA};
+use mmrcms_template::{
+	locale::Label,
+	template::{cache_manga_page, MMRCMSSource, CACHED_MANGA},
+};
 
 lazy_static! {
 	static ref INSTANCE: MMRCMSSource<'static> = MMRCMSSource {
 		base_url: "http://animaregia.net",
 		lang: "pt-BR",
-		category: "Categoria",
+		artist: "Artista",
+		alphabet: "Alfabeto",
+		listing_latest: "Últimos Lançamentos",
+		listing_hot: "Em Alta",
+		listing_popular: "Populares",
 		..Default::default()
 	};
 }
@@ -33,6 +40,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	Ok(result)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	INSTANCE.get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	let url = format!("{}/{}/{}", INSTANCE.base_url, INSTANCE.manga_path, id);
@@ -55,12 +67,13 @@ fn get_manga_details(id: String) -> Result<Manga> {
 		..Default::default()
 	};
 
+	let locale = INSTANCE.locale();
 	for elem in html.select("li.list-group-item").array() {
 		if let Ok(node) = elem.as_node() {
 			let text = node.text().read().to_lowercase();
 			let end = text.find(':').unwrap_or(0);
-			match &text.as_str()[..end] {
-				"autor(es)" => {
+			match locale.label(&text.as_str()[..end]) {
+				Some(Label::Author) => {
 					manga.author = node
 						.select("a")
 						.array()
@@ -71,7 +84,7 @@ fn get_manga_details(id: String) -> Result<Manga> {
 						.collect::<Vec<_>>()
 						.join(", ")
 				}
-				"artist(s)" => {
+				Some(Label::Artist) => {
 					manga.artist = node
 						.select("a")
 						.array()
@@ -82,17 +95,19 @@ fn get_manga_details(id: String) -> Result<Manga> {
 						.collect::<Vec<_>>()
 						.join(", ")
 				}
-				"categorias" => node.select("a").array().for_each(|elem| {
+				Some(Label::Categories) => node.select("a").array().for_each(|elem| {
 					if let Ok(node) = elem.as_node() {
 						manga.categories.push(node.text().read());
 					}
 				}),
-				"status" => {
-					manga.status = match node.select("span.label").text().read().trim() {
-						"Completo" | "Concluído" => aidoku::MangaStatus::Completed,
-						"Ativo" => aidoku::MangaStatus::Ongoing,
-						_ => aidoku::MangaStatus::Unknown,
-					}
+				Some(Label::Status) => {
+					manga.status = locale.status(
+						node.select("span.label")
+							.text()
+							.read()
+							.to_lowercase()
+							.trim(),
+					)
 				}
 				_ => continue,
 			}
@@ -3,7 +3,8 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaContentRating, MangaPageResult, MangaViewer, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaContentRating, MangaPageResult, MangaViewer,
+	Page,
 };
 use lazy_static::lazy_static;
 use mmrcms_template::template::MMRCMSSource;
@@ -12,7 +13,11 @@ lazy_static! {
 	static ref INSTANCE: MMRCMSSource<'static> = MMRCMSSource {
 		base_url: "https://hentai.gekkouscans.com.br",
 		lang: "pt-BR",
-		category: "Categoria",
+		artist: "Artista",
+		alphabet: "Alfabeto",
+		listing_latest: "Últimos Lançamentos",
+		listing_hot: "Em Alta",
+		listing_popular: "Populares",
 		category_parser: |_, categories| {
 			let mut viewer = MangaViewer::Rtl;
 			for category in categories {
@@ -23,15 +28,6 @@ lazy_static! {
 			}
 			(MangaContentRating::Nsfw, viewer)
 		},
-		category_mapper: |idx| {
-			if idx == 0 {
-				String::new()
-			} else if (1..=7).contains(&idx) {
-				String::from(itoa::Buffer::new().format(idx))
-			} else {
-				String::from(itoa::Buffer::new().format(idx + 1))
-			}
-		},
 		..Default::default()
 	};
 }
@@ -41,6 +37,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	INSTANCE.get_manga_list(filters, page)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	INSTANCE.get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	INSTANCE.get_manga_details(id)
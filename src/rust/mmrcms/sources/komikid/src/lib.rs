@@ -4,14 +4,10 @@ use mmrcms_template::{mmrcms, template::MMRCMSSource};
 mmrcms! {
 	MMRCMSSource {
 		base_url: "https://www.komikid.com",
-		category_mapper: |idx| {
-			match idx {
-				0 => String::new(),
-				1..=5 => String::from(itoa::Buffer::new().format(idx)),
-				6..=31 => String::from(itoa::Buffer::new().format(idx + 1)),
-				_ => String::new(),
-			}
-		},
+		lang: "id",
+		listing_latest: "Terbaru",
+		listing_hot: "Hot",
+		listing_popular: "Populer",
 		..Default::default()
 	}
 }
@@ -6,6 +6,11 @@ mmrcms! {
 	MMRCMSSource {
 		base_url: "http://mangadoor.com",
 		lang: "pt-BR",
+		artist: "Artista",
+		alphabet: "Alfabeto",
+		listing_latest: "Últimos Lançamentos",
+		listing_hot: "Em Alta",
+		listing_popular: "Populares",
 		category_parser: |_, categories| {
 			let mut nsfw = MangaContentRating::Safe;
 			let mut viewer = MangaViewer::Rtl;
@@ -24,15 +29,6 @@ mmrcms! {
 			}
 			(nsfw, viewer)
 		},
-		category_mapper: |idx| {
-			if idx == 0 {
-				String::new()
-			} else if (1..=3).contains(&idx) {
-				String::from(itoa::Buffer::new().format(idx))
-			} else {
-				String::from(itoa::Buffer::new().format(idx + 1))
-			}
-		},
 		..Default::default()
 	}
 }
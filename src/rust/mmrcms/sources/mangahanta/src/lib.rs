@@ -6,7 +6,11 @@ mmrcms! {
 	MMRCMSSource {
 		base_url: "http://mangahanta.com",
 		lang: "tr",
-		category: "Kategori",
+		artist: "Çizer",
+		alphabet: "Alfabe",
+		listing_latest: "Son Eklenenler",
+		listing_hot: "Sıcak",
+		listing_popular: "Popüler",
 		category_parser: |_, categories| {
 			let mut nsfw = MangaContentRating::Safe;
 			let mut viewer = MangaViewer::Rtl;
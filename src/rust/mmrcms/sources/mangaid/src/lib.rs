@@ -3,7 +3,7 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{defaults::defaults_get, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaPageResult, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 };
 use lazy_static::lazy_static;
 use mmrcms_template::template::MMRCMSSource;
@@ -11,15 +11,10 @@ use mmrcms_template::template::MMRCMSSource;
 lazy_static! {
 	static ref INSTANCE: MMRCMSSource<'static> = MMRCMSSource {
 		base_url: "https://mangaid.click",
-		category_mapper: |idx| {
-			if idx == 0 {
-				String::new()
-			} else if (1..=16).contains(&idx) {
-				String::from(itoa::Buffer::new().format(idx))
-			} else {
-				String::from(itoa::Buffer::new().format(idx + 1))
-			}
-		},
+		lang: "id",
+		listing_latest: "Terbaru",
+		listing_hot: "Hot",
+		listing_popular: "Populer",
 		..Default::default()
 	};
 }
@@ -29,6 +24,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	INSTANCE.get_manga_list(filters, page)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	INSTANCE.get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	INSTANCE.get_manga_details(id)
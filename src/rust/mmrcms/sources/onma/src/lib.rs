@@ -3,19 +3,25 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaContentRating, MangaPageResult, MangaStatus,
-	MangaViewer, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaContentRating, MangaPageResult, MangaViewer,
+	Page,
 };
 use lazy_static::lazy_static;
-use mmrcms_template::template::{cache_manga_page, MMRCMSSource, CACHED_MANGA};
+use mmrcms_template::{
+	locale::Label,
+	template::{cache_manga_page, MMRCMSSource, CACHED_MANGA},
+};
 
 lazy_static! {
 	static ref INSTANCE: MMRCMSSource<'static> = MMRCMSSource {
 		base_url: "https://onma.me",
 		lang: "ar",
 
-		category: "الفئة",
-		tags: "العلامات",
+		artist: "الرسام",
+		alphabet: "الأبجدية",
+		listing_latest: "آخر الإصدارات",
+		listing_hot: "الرائج",
+		listing_popular: "الأكثر شعبية",
 		category_parser: |_, categories| {
 			let mut nsfw = MangaContentRating::Safe;
 			let mut viewer = MangaViewer::Rtl;
@@ -39,6 +45,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	INSTANCE.get_manga_list(filters, page)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	INSTANCE.get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	let url = format!("{}/{}/{}", INSTANCE.base_url, INSTANCE.manga_path, id);
@@ -56,22 +67,25 @@ fn get_manga_details(id: String) -> Result<Manga> {
 		url,
 		..Default::default()
 	};
+	let locale = INSTANCE.locale();
 	for elem in html.select("div.col-md-6 h3").array() {
 		if let Ok(node) = elem.as_node() {
 			let text = node.text().read().to_lowercase();
 			let end = text.find(" : ").unwrap_or(0);
-			match &text.as_str()[..end] {
-				"النوع" => manga.categories.push(node.select("div").text().read()),
-				"المؤلف" => manga.author = node.select("div").text().read(),
-				"الرسام" => manga.artist = node.select("div").text().read(),
-				"الحالة" => {
-					manga.status = match node.select("span.label").text().read().trim() {
-						"مستمرة" => MangaStatus::Ongoing,
-						"مكتملة" => MangaStatus::Completed,
-						_ => MangaStatus::Unknown,
-					}
+			match locale.label(&text.as_str()[..end]) {
+				Some(Label::Type) => manga.categories.push(node.select("div").text().read()),
+				Some(Label::Author) => manga.author = node.select("div").text().read(),
+				Some(Label::Artist) => manga.artist = node.select("div").text().read(),
+				Some(Label::Status) => {
+					manga.status = locale.status(
+						node.select("span.label")
+							.text()
+							.read()
+							.to_lowercase()
+							.trim(),
+					)
 				}
-				"التصنيفات" => node.select("a").array().for_each(|elem| {
+				Some(Label::Categories) => node.select("a").array().for_each(|elem| {
 					if let Ok(node) = elem.as_node() {
 						manga.categories.push(node.text().read());
 					}
@@ -6,17 +6,11 @@ mmrcms! {
 	MMRCMSSource {
 		base_url: "https://phoenix-scans.pl",
 		lang: "pl",
-		category: "Kategorii",
-		tags: "Tagów",
-		category_mapper: |idx| {
-			match idx {
-				0 => String::new(),
-				1..=21 => String::from(itoa::Buffer::new().format(idx + 32)),
-				22..=30 => String::from(itoa::Buffer::new().format(idx + 34)),
-				31..=32 => String::from(itoa::Buffer::new().format(idx + 35)),
-				_ => String::new(),
-			}
-		},
+		artist: "Rysownik",
+		alphabet: "Alfabet",
+		listing_latest: "Najnowsze",
+		listing_hot: "Gorące",
+		listing_popular: "Popularne",
 		category_parser: |_, categories| {
 			let mut nsfw = MangaContentRating::Safe;
 			let mut viewer = MangaViewer::Rtl;
@@ -30,16 +24,6 @@ mmrcms! {
 			}
 			(nsfw, viewer)
 		},
-		tags_mapper: |idx| {
-			String::from(match idx {
-				1 => "aktywne", // Active
-				2 => "zakonczone", // Completed
-				3 => "porzucone", // Dropped
-				4 => "zawieszone", // Suspended
-				5 => "zlicencjonowane", // zlicencjonowane
-				_ => "",
-			})
-		},
 		..Default::default()
 	}
 }
@@ -21,3 +21,33 @@ pub fn append_protocol(url: String) -> String {
 		url
 	}
 }
+
+/// Lowercase the text and strip diacritics so that searches match regardless
+/// of case and accents (e.g. "Ação" and "acao", "Işık" and "isik")
+pub fn fold(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+	for c in text.chars().flat_map(char::to_lowercase) {
+		match c {
+			// Combining diacritical marks, e.g. the dot left by lowercasing "İ"
+			'\u{0300}'..='\u{036f}' => continue,
+			'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => result.push('a'),
+			'ç' | 'ć' | 'č' => result.push('c'),
+			'ď' | 'đ' => result.push('d'),
+			'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => result.push('e'),
+			'ğ' => result.push('g'),
+			'ì' | 'í' | 'î' | 'ï' | 'ı' | 'ī' | 'į' => result.push('i'),
+			'ł' => result.push('l'),
+			'ñ' | 'ń' | 'ň' => result.push('n'),
+			'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => result.push('o'),
+			'ř' => result.push('r'),
+			'ś' | 'ş' | 'ș' | 'š' => result.push('s'),
+			'ß' => result.push_str("ss"),
+			'ţ' | 'ț' | 'ť' => result.push('t'),
+			'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => result.push('u'),
+			'ý' | 'ÿ' => result.push('y'),
+			'ź' | 'ż' | 'ž' => result.push('z'),
+			c => result.push(c),
+		}
+	}
+	result
+}
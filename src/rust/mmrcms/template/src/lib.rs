@@ -1,6 +1,7 @@
 #![no_std]
 #![feature(stmt_expr_attributes)]
 pub mod helper;
+pub mod locale;
 pub mod template;
 
 #[macro_export]
@@ -10,7 +11,7 @@ macro_rules! mmrcms {
 			error::Result,
 			prelude::*,
 			std::{net::Request, String, Vec},
-			Chapter, DeepLink, Filter, Manga, MangaPageResult, Page,
+			Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 		};
 		use lazy_static::lazy_static;
 
@@ -23,6 +24,11 @@ macro_rules! mmrcms {
 			INSTANCE.get_manga_list(filters, page)
 		}
 
+		#[get_manga_listing]
+		fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+			INSTANCE.get_manga_listing(listing, page)
+		}
+
 		#[get_manga_details]
 		fn get_manga_details(id: String) -> Result<Manga> {
 			INSTANCE.get_manga_details(id)
@@ -0,0 +1,318 @@
+use aidoku::{
+	std::{String, StringRef},
+	MangaStatus,
+};
+
+/// Per-language strings of a MMRCMS instance.
+///
+/// Labels and status words are lowercase and compared against the
+/// lowercased page text, with the label's trailing colon removed.
+#[derive(Clone, Copy)]
+pub struct MMRCMSLocale<'a> {
+	pub author: &'a [&'a str],
+	pub artist: &'a [&'a str],
+	pub categories: &'a [&'a str],
+	pub status: &'a [&'a str],
+	pub manga_type: &'a [&'a str],
+	pub other_names: &'a [&'a str],
+	pub release_year: &'a [&'a str],
+
+	pub status_ongoing: &'a [&'a str],
+	pub status_completed: &'a [&'a str],
+	pub status_hiatus: &'a [&'a str],
+	pub status_cancelled: &'a [&'a str],
+
+	/// Chapter date formats, tried in order
+	pub date_formats: &'a [&'a str],
+	pub date_locale: &'a str,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+	Author,
+	Artist,
+	Categories,
+	Status,
+	Type,
+	OtherNames,
+	ReleaseYear,
+}
+
+const DATE_FORMATS: &[&str] = &["dd MMM'.' yyyy", "dd MMM yyyy", "dd/MM/yyyy"];
+
+pub const EN: MMRCMSLocale = MMRCMSLocale {
+	author: &["author(s)", "author"],
+	artist: &["artist(s)", "artist"],
+	categories: &["categories", "tags"],
+	status: &["status"],
+	manga_type: &["type"],
+	other_names: &["other names", "alternative names"],
+	release_year: &["date of release", "release year"],
+	status_ongoing: &["ongoing"],
+	status_completed: &["complete", "completed"],
+	status_hiatus: &["hiatus"],
+	status_cancelled: &["cancelled", "canceled"],
+	date_formats: DATE_FORMATS,
+	date_locale: "en_US",
+};
+
+pub const PT_BR: MMRCMSLocale = MMRCMSLocale {
+	author: &["autor(es)", "autor"],
+	artist: &["artista(s)", "artista"],
+	categories: &["categorias"],
+	status: &["status", "estado"],
+	manga_type: &["tipo", "ttipo"],
+	other_names: &["nomes alternativos", "outros nomes"],
+	release_year: &["data de lançamento", "ano de lançamento"],
+	status_ongoing: &["em lançamento", "ativo", "em andamento"],
+	status_completed: &["completo", "concluído"],
+	status_hiatus: &["hiato", "pausado"],
+	status_cancelled: &["cancelado"],
+	date_formats: DATE_FORMATS,
+	date_locale: "pt_BR",
+};
+
+pub const ES: MMRCMSLocale = MMRCMSLocale {
+	author: &["autor(es)", "autor"],
+	artist: &["artista(s)", "artista"],
+	categories: &["categorías"],
+	status: &["estado"],
+	manga_type: &["tipo", "ttipo"],
+	other_names: &["otros nombres"],
+	release_year: &["fecha de lanzamiento"],
+	status_ongoing: &["en curso"],
+	status_completed: &["completo"],
+	status_hiatus: &["pausado"],
+	status_cancelled: &["cancelado"],
+	date_formats: DATE_FORMATS,
+	date_locale: "es_ES",
+};
+
+pub const FR: MMRCMSLocale = MMRCMSLocale {
+	author: &["auteur(s)"],
+	artist: &["artiste(s)"],
+	categories: &["catégories"],
+	status: &["statut"],
+	manga_type: &["type"],
+	other_names: &["autres noms"],
+	release_year: &["date de sortie"],
+	status_ongoing: &["en cours"],
+	status_completed: &["complet", "terminé"],
+	status_hiatus: &["en pause"],
+	status_cancelled: &["abandonné"],
+	date_formats: DATE_FORMATS,
+	date_locale: "fr_FR",
+};
+
+pub const JA: MMRCMSLocale = MMRCMSLocale {
+	author: &["著作"],
+	artist: &["作画"],
+	categories: &["ジャンル"],
+	status: &["状態"],
+	manga_type: &["タイプ"],
+	other_names: &["別名"],
+	release_year: &["発売日"],
+	status_ongoing: &["連載中"],
+	status_completed: &["完結"],
+	status_hiatus: &["休載"],
+	status_cancelled: &["打ち切り"],
+	date_formats: DATE_FORMATS,
+	date_locale: "ja_JP",
+};
+
+pub const TR: MMRCMSLocale = MMRCMSLocale {
+	author: &["yazar(lar)", "mangaka(lar)"],
+	artist: &["sanatçi(lar)", "sanatçı(lar)"],
+	categories: &["kategoriler"],
+	status: &["durum"],
+	manga_type: &["tür"],
+	other_names: &["diğer adları", "diğer adlar"],
+	release_year: &["yayın tarihi", "çıkış tarihi"],
+	status_ongoing: &["devam ediyor"],
+	status_completed: &["tamamlandı"],
+	status_hiatus: &["ara verildi"],
+	status_cancelled: &["iptal edildi"],
+	date_formats: DATE_FORMATS,
+	date_locale: "tr_TR",
+};
+
+pub const ID: MMRCMSLocale = MMRCMSLocale {
+	author: &["pengarang/penulis", "pengarang", "penulis"],
+	artist: &["artist(s)/ilustrator", "seniman"],
+	categories: &["kategori"],
+	status: &["status"],
+	manga_type: &["tipe"],
+	other_names: &["nama lain"],
+	release_year: &["tanggal rilis", "tahun rilis"],
+	status_ongoing: &["berjalan", "bersambung"],
+	status_completed: &["tamat", "selesai"],
+	status_hiatus: &["hiatus"],
+	status_cancelled: &["dibatalkan"],
+	date_formats: DATE_FORMATS,
+	date_locale: "id_ID",
+};
+
+pub const AR: MMRCMSLocale = MMRCMSLocale {
+	author: &["المؤلف"],
+	artist: &["الرسام"],
+	categories: &["التصنيفات"],
+	status: &["الحالة"],
+	manga_type: &["النوع"],
+	other_names: &["أسماء أخرى"],
+	release_year: &["تاريخ الإصدار"],
+	status_ongoing: &["مستمرة"],
+	status_completed: &["مكتملة"],
+	status_hiatus: &["متوقفة"],
+	status_cancelled: &["ملغاة"],
+	date_formats: DATE_FORMATS,
+	date_locale: "ar",
+};
+
+pub const RU: MMRCMSLocale = MMRCMSLocale {
+	author: &["автор", "перевод"],
+	artist: &["художник"],
+	categories: &["жанр", "жанры"],
+	status: &["статус"],
+	manga_type: &["тип"],
+	other_names: &["другие названия"],
+	release_year: &["дата выпуска", "год выпуска"],
+	status_ongoing: &["выпускается", "продолжается"],
+	status_completed: &["завершён", "завершен"],
+	status_hiatus: &["приостановлен"],
+	status_cancelled: &["заброшен"],
+	date_formats: DATE_FORMATS,
+	date_locale: "ru_RU",
+};
+
+pub const PL: MMRCMSLocale = MMRCMSLocale {
+	author: &["autor/autorzy", "autor"],
+	artist: &["rysownik/rysownicy", "rysownik"],
+	categories: &["kategorie", "tagi"],
+	status: &["status"],
+	manga_type: &["typ"],
+	other_names: &["inne nazwy", "alternatywne tytuły"],
+	release_year: &["data wydania", "rok wydania"],
+	status_ongoing: &["prace w toku"],
+	status_completed: &["zakończone"],
+	status_hiatus: &["wstrzymane"],
+	status_cancelled: &["porzucone"],
+	date_formats: DATE_FORMATS,
+	date_locale: "pl_PL",
+};
+
+pub const BG: MMRCMSLocale = MMRCMSLocale {
+	author: &["автор"],
+	artist: &["художник"],
+	categories: &["жанр", "категории"],
+	status: &["статус"],
+	manga_type: &["тип"],
+	other_names: &["други имена"],
+	release_year: &["година на издаване"],
+	status_ongoing: &["продължава"],
+	status_completed: &["завършена"],
+	status_hiatus: &["в пауза"],
+	status_cancelled: &["прекратена"],
+	date_formats: DATE_FORMATS,
+	date_locale: "bg_BG",
+};
+
+pub const KO: MMRCMSLocale = MMRCMSLocale {
+	author: &["작가"],
+	artist: &["그림"],
+	categories: &["장르"],
+	status: &["상태"],
+	manga_type: &["유형"],
+	other_names: &["다른 이름"],
+	release_year: &["출시일"],
+	status_ongoing: &["연재중", "연재 중"],
+	status_completed: &["완결"],
+	status_hiatus: &["휴재"],
+	status_cancelled: &["연재 중단"],
+	date_formats: DATE_FORMATS,
+	date_locale: "ko_KR",
+};
+
+/// Every table, tried in this order after a source's own.
+const LOCALES: [MMRCMSLocale; 12] = [EN, PT_BR, ES, FR, JA, TR, ID, AR, RU, PL, BG, KO];
+
+impl<'a> MMRCMSLocale<'a> {
+	/// Table for a source language, defaulting to English.
+	pub fn from_lang(lang: &str) -> MMRCMSLocale<'static> {
+		match lang.split('-').next().unwrap_or_default() {
+			"pt" => PT_BR,
+			"es" => ES,
+			"fr" => FR,
+			"ja" => JA,
+			"tr" => TR,
+			"id" => ID,
+			"ar" => AR,
+			"ru" => RU,
+			"pl" => PL,
+			"bg" => BG,
+			"ko" => KO,
+			_ => EN,
+		}
+	}
+
+	fn find_label(&self, label: &str) -> Option<Label> {
+		[
+			(self.author, Label::Author),
+			(self.artist, Label::Artist),
+			(self.categories, Label::Categories),
+			(self.status, Label::Status),
+			(self.manga_type, Label::Type),
+			(self.other_names, Label::OtherNames),
+			(self.release_year, Label::ReleaseYear),
+		]
+		.iter()
+		.find(|(labels, _)| labels.contains(&label))
+		.map(|(_, kind)| *kind)
+	}
+
+	/// Kind of a lowercased details label. Many instances keep some labels
+	/// in English or show pages in another language than their own, so the
+	/// other tables are tried after this one.
+	pub fn label(&self, label: &str) -> Option<Label> {
+		self.find_label(label)
+			.or_else(|| LOCALES.iter().find_map(|locale| locale.find_label(label)))
+	}
+
+	fn find_status(&self, status: &str) -> Option<MangaStatus> {
+		if self.status_ongoing.contains(&status) {
+			Some(MangaStatus::Ongoing)
+		} else if self.status_completed.contains(&status) {
+			Some(MangaStatus::Completed)
+		} else if self.status_hiatus.contains(&status) {
+			Some(MangaStatus::Hiatus)
+		} else if self.status_cancelled.contains(&status) {
+			Some(MangaStatus::Cancelled)
+		} else {
+			None
+		}
+	}
+
+	/// Status of a lowercased status word, looked up like `label`.
+	pub fn status(&self, status: &str) -> MangaStatus {
+		self.find_status(status)
+			.or_else(|| LOCALES.iter().find_map(|locale| locale.find_status(status)))
+			.unwrap_or(MangaStatus::Unknown)
+	}
+
+	/// Parse a chapter date with this locale's formats, then the
+	/// English ones, returning -1 if none matches.
+	pub fn parse_date(&self, date: &str) -> f64 {
+		let date = String::from(date.trim());
+		for (formats, locale) in [
+			(self.date_formats, self.date_locale),
+			(EN.date_formats, EN.date_locale),
+		] {
+			for format in formats {
+				let timestamp = StringRef::from(&date).as_date(format, Some(locale), None);
+				if timestamp > 0.0 {
+					return timestamp;
+				}
+			}
+		}
+		-1.0
+	}
+}
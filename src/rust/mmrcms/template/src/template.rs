@@ -6,24 +6,48 @@ use aidoku::{
 		html::Node,
 		json,
 		net::{HttpMethod, Request},
-		ObjectRef, String, Vec,
+		current_date, ObjectRef, String, Vec,
 	},
-	Chapter, DeepLink, Filter, FilterType, Manga, MangaContentRating, MangaPageResult, MangaStatus,
+	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaContentRating, MangaPageResult,
 	MangaViewer, Page,
 };
 
-use crate::helper::{append_protocol, extract_f32_from_string};
+use crate::{
+	helper::{append_protocol, extract_f32_from_string, fold},
+	locale::{Label, MMRCMSLocale},
+};
 
 pub static mut CACHED_MANGA: Option<Node> = None;
 static mut CACHED_MANGA_ID: Option<String> = None;
 
-/// Internal attribute to control if the source should fall
-/// back to self searching after failing to use the search
-/// engine first time.
+/// Time after which the search engine may be used again.
 ///
-/// Strikes a balance between control and reliability (and also
-/// not spamming sources with useless requests)
-static mut INTERNAL_USE_SEARCH_ENGINE: bool = true;
+/// When the search engine fails the source falls back to self
+/// searching for a while instead of giving up on it for good, which
+/// strikes a balance between reliability and not spamming sources
+/// with useless requests.
+static mut SEARCH_ENGINE_RETRY_AT: f64 = 0.0;
+const SEARCH_ENGINE_RETRY_INTERVAL: f64 = 600.0;
+
+/// Title searched for last, and whether its first page came from the
+/// search engine. Later pages of the same query keep to that path.
+static mut SEARCHED_WITH_ENGINE: Option<(String, bool)> = None;
+
+/// Manga directory from `changeMangaList`, used for self searching.
+static mut CACHED_DIRECTORY: Option<Vec<DirectoryEntry>> = None;
+static mut CACHED_DIRECTORY_AT: f64 = 0.0;
+const DIRECTORY_TTL: f64 = 1800.0;
+
+const SEARCH_PAGE_SIZE: usize = 20;
+
+#[derive(Clone)]
+struct DirectoryEntry {
+	id: String,
+	title: String,
+	/// Case and diacritic folded title used for matching
+	folded_title: String,
+	url: String,
+}
 
 pub fn cache_manga_page(url: &str) {
 	unsafe {
@@ -42,16 +66,20 @@ pub fn cache_manga_page(url: &str) {
 pub struct MMRCMSSource<'a> {
 	pub base_url: &'a str,
 	pub lang: &'a str,
+	/// Labels, status words and date formats; picked from `lang` if unset
+	pub locale: Option<MMRCMSLocale<'a>>,
 	/// {base_url}/{manga_path}/{manga_id}
 	pub manga_path: &'a str,
 
-	/// Localization
-	pub category: &'a str,
-	pub tags: &'a str,
+	/// Localization, must match the filter names in filters.json
+	pub artist: &'a str,
+	pub alphabet: &'a str,
+	/// Listing names, as declared in source.json
+	pub listing_latest: &'a str,
+	pub listing_hot: &'a str,
+	pub listing_popular: &'a str,
 
 	pub category_parser: fn(&Node, Vec<String>) -> (MangaContentRating, MangaViewer),
-	pub category_mapper: fn(i64) -> String,
-	pub tags_mapper: fn(i64) -> String,
 
 	pub use_search_engine: bool,
 }
@@ -78,10 +106,14 @@ impl<'a> Default for MMRCMSSource<'a> {
 		MMRCMSSource {
 			base_url: "",
 			lang: "en",
+			locale: None,
 			manga_path: "manga",
 
-			category: "Category",
-			tags: "Tag",
+			artist: "Artist",
+			alphabet: "Alphabet",
+			listing_latest: "Latest",
+			listing_hot: "Hot",
+			listing_popular: "Popular",
 
 			category_parser: |_, categories| {
 				let mut nsfw = MangaContentRating::Safe;
@@ -103,20 +135,17 @@ impl<'a> Default for MMRCMSSource<'a> {
 				}
 				(nsfw, viewer)
 			},
-			category_mapper: |idx| {
-				if idx != 0 {
-					String::from(itoa::Buffer::new().format(idx))
-				} else {
-					String::new()
-				}
-			}, // 0 is reserved for None
-			tags_mapper: |_| String::new(),
 			use_search_engine: true,
 		}
 	}
 }
 
 impl<'a> MMRCMSSource<'a> {
+	pub fn locale(&self) -> MMRCMSLocale<'a> {
+		self.locale
+			.unwrap_or_else(|| MMRCMSLocale::from_lang(self.lang))
+	}
+
 	fn guess_cover(&self, url: &str, id: &str) -> String {
 		if url.ends_with("no-image.png") || url.is_empty() {
 			format!(
@@ -128,44 +157,71 @@ impl<'a> MMRCMSSource<'a> {
 		}
 	}
 
-	fn self_search<T: AsRef<str>>(&self, query: T) -> Result<MangaPageResult> {
-		let query = query.as_ref();
+	fn refresh_directory(&self) -> Result<()> {
+		let now = current_date();
+		unsafe {
+			if CACHED_DIRECTORY.is_some() && now - CACHED_DIRECTORY_AT < DIRECTORY_TTL {
+				return Ok(());
+			}
+		}
 		let html = Request::new(
 			format!("{}/changeMangaList?type=text", self.base_url),
 			HttpMethod::Get,
 		)
 		.html()?;
 		decode_cfemail(&html);
-		let manga = html
+		let directory = html
 			.select("ul.manga-list a")
 			.array()
 			.filter_map(|elem| {
-				if let Ok(node) = elem.as_node()
-				   && let Ok(title) = elem.as_node().map(|v| v.text().read())
-				   && title.to_lowercase().contains(query) {
-					let url = node.attr("abs:href").read();
-					let id = url
-						.split('/')
-						.last()
-						.map(String::from)
-						.unwrap_or_else(|| url.replace(&format!("{}/{}", self.base_url, self.manga_path), ""));
-					let cover = self.guess_cover("", &id);
-					Some(Manga {
-						id,
-						cover,
-						title,
-						url,
-						..Default::default()
-					})
-				} else {
-					None
-				}
+				let node = elem.as_node().ok()?;
+				let title = node.text().read();
+				let url = node.attr("abs:href").read();
+				let id = url
+					.split('/')
+					.last()
+					.map(String::from)
+					.unwrap_or_else(|| url.replace(&format!("{}/{}", self.base_url, self.manga_path), ""));
+				Some(DirectoryEntry {
+					id,
+					folded_title: fold(&title),
+					title,
+					url,
+				})
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			CACHED_DIRECTORY = Some(directory);
+			CACHED_DIRECTORY_AT = now;
+		}
+		Ok(())
+	}
+
+	fn self_search<T: AsRef<str>>(&self, query: T, page: i32) -> Result<MangaPageResult> {
+		let query = fold(query.as_ref());
+		self.refresh_directory()?;
+		let directory = unsafe { CACHED_DIRECTORY.as_deref().unwrap_or_default() };
+		let matches = directory
+			.iter()
+			.filter(|entry| entry.folded_title.contains(query.as_str()))
+			.collect::<Vec<_>>();
+		let start = (page.max(1) as usize - 1) * SEARCH_PAGE_SIZE;
+		let manga = matches
+			.iter()
+			.skip(start)
+			.take(SEARCH_PAGE_SIZE)
+			.map(|entry| Manga {
+				id: entry.id.clone(),
+				cover: self.guess_cover("", &entry.id),
+				title: entry.title.clone(),
+				url: entry.url.clone(),
+				..Default::default()
 			})
 			.collect::<Vec<_>>();
 
 		Ok(MangaPageResult {
 			manga,
-			has_more: false,
+			has_more: matches.len() > start + SEARCH_PAGE_SIZE,
 		})
 	}
 
@@ -183,19 +239,24 @@ impl<'a> MMRCMSSource<'a> {
 					if t.is_empty() {
 						continue;
 					}
-					title = encode_uri_component(t);
+					title = t;
 					break;
 				}
-				FilterType::Author => query.push(format!(
-					"artist={}",
-					encode_uri_component(
-						filter
-							.value
-							.as_string()
-							.map(|v| v.read())
-							.unwrap_or_default()
-					)
-				)),
+				FilterType::Author | FilterType::Text => {
+					let key = match filter.kind {
+						FilterType::Author => "author",
+						_ if filter.name == self.artist => "artist",
+						_ => continue,
+					};
+					let value = filter
+						.value
+						.as_string()
+						.map(|v| v.read())
+						.unwrap_or_default();
+					if !value.is_empty() {
+						query.push(format!("{}={}", key, encode_uri_component(value)));
+					}
+				}
 				FilterType::Sort => {
 					if let Ok(value) = filter.value.as_object() {
 						let index = value.get("index").as_int().unwrap_or(0);
@@ -209,24 +270,57 @@ impl<'a> MMRCMSSource<'a> {
 						query.push(format!("asc={}", if asc { "true" } else { "false" }));
 					}
 				}
-				FilterType::Select => {
-					let value = filter.value.as_int().unwrap_or(-1);
-					match filter.name.as_str() {
-						x if x == self.category => {
-							query.push(format!("cat={}", (self.category_mapper)(value)))
-						}
-						x if x == self.tags => {
-							query.push(format!("tag={}", (self.tags_mapper)(value)))
+				FilterType::Select if filter.name == self.alphabet => {
+					// None, then `#` for titles not starting with a letter, then A to Z
+					match filter.value.as_int().unwrap_or(-1) {
+						1 => query.push(String::from("alpha=Other")),
+						index @ 2..=27 => {
+							query.push(format!("alpha={}", (b'A' + (index - 2) as u8) as char))
 						}
 						_ => continue,
 					}
 				}
+				FilterType::Genre => {
+					// Ids are query pairs like `cat=5` or `tag=action`, every
+					// selected one is sent.
+					if filter.value.as_int().unwrap_or(-1) != 1 {
+						continue;
+					}
+					if let Ok(id) = filter.object.get("id").as_string() {
+						let id = id.read();
+						if id.contains('=') && !query.contains(&id) {
+							query.push(id);
+						}
+					}
+				}
 				_ => continue,
 			}
 		}
 		if !title.is_empty() {
-			if self.use_search_engine && unsafe { INTERNAL_USE_SEARCH_ENGINE } {
-				let url = format!("{}/search?query={}", self.base_url, title);
+			// Decided on the first page only, so a query doesn't switch paths
+			// when the retry window ends between two of its pages.
+			let use_engine = match unsafe { &SEARCHED_WITH_ENGINE } {
+				Some((searched, engine)) if page > 1 && *searched == title => *engine,
+				_ => {
+					page == 1
+						&& self.use_search_engine
+						&& current_date() >= unsafe { SEARCH_ENGINE_RETRY_AT }
+				}
+			};
+			unsafe { SEARCHED_WITH_ENGINE = Some((title.clone(), use_engine)) };
+			if use_engine {
+				if page > 1 {
+					// The search engine returns every suggestion at once
+					return Ok(MangaPageResult {
+						manga: Vec::new(),
+						has_more: false,
+					});
+				}
+				let url = format!(
+					"{}/search?query={}",
+					self.base_url,
+					encode_uri_component(title.clone())
+				);
 				if let Ok(obj) = Request::new(&url, HttpMethod::Get).json()
 				   && let Ok(json) = obj.as_object()
 				   && let Ok(suggestions) = json.get("suggestions").as_array() {
@@ -248,11 +342,14 @@ impl<'a> MMRCMSSource<'a> {
 						has_more: false,
 					})
 				} else {
-					unsafe { INTERNAL_USE_SEARCH_ENGINE = false };
-					self.self_search(title)
+					unsafe {
+						SEARCH_ENGINE_RETRY_AT = current_date() + SEARCH_ENGINE_RETRY_INTERVAL;
+						SEARCHED_WITH_ENGINE = Some((title.clone(), false));
+					};
+					self.self_search(title, page)
 				}
 			} else {
-				self.self_search(title)
+				self.self_search(title, page)
 			}
 		} else {
 			let url = format!(
@@ -261,41 +358,124 @@ impl<'a> MMRCMSSource<'a> {
 				itoa::Buffer::new().format(page),
 				query.join("&")
 			);
-			let html = Request::new(&url, HttpMethod::Get).html()?;
-			decode_cfemail(&html);
-			let node = html.select("div[class^=col-sm-]");
-			let elems = node.array();
-			let mut manga = Vec::with_capacity(elems.len());
-			let has_more: bool = !elems.is_empty();
-
-			for elem in elems {
-				if let Ok(manga_node) = elem.as_node() {
-					let url = manga_node
-						.select(format!("a[href*='{}/{}']", self.base_url, self.manga_path))
-						.attr("abs:href")
-						.read();
-					let id = url.replace(&format!("{}/{}/", self.base_url, self.manga_path), "");
-					let cover = self.guess_cover(
-						&manga_node
-							.select(format!(
-								"a[href*='{}/{}'] img",
-								self.base_url, self.manga_path
-							))
-							.attr("abs:src")
-							.read(),
-						&id,
-					);
-					let title = manga_node.select("a.chart-title strong").text().read();
-					manga.push(Manga {
-						id: id.clone(),
-						cover,
-						title,
-						url,
-						..Default::default()
-					});
+			self.parse_filter_list(&url)
+		}
+	}
+
+	fn parse_filter_list(&self, url: &str) -> Result<MangaPageResult> {
+		let html = Request::new(url, HttpMethod::Get).html()?;
+		decode_cfemail(&html);
+		let node = html.select("div[class^=col-sm-]");
+		let elems = node.array();
+		let mut manga = Vec::with_capacity(elems.len());
+		let has_more: bool = !elems.is_empty();
+
+		for elem in elems {
+			if let Ok(manga_node) = elem.as_node() {
+				let url = manga_node
+					.select(format!("a[href*='{}/{}']", self.base_url, self.manga_path))
+					.attr("abs:href")
+					.read();
+				let id = url.replace(&format!("{}/{}/", self.base_url, self.manga_path), "");
+				let cover = self.guess_cover(
+					&manga_node
+						.select(format!(
+							"a[href*='{}/{}'] img",
+							self.base_url, self.manga_path
+						))
+						.attr("abs:src")
+						.read(),
+					&id,
+				);
+				let title = manga_node.select("a.chart-title strong").text().read();
+				manga.push(Manga {
+					id: id.clone(),
+					cover,
+					title,
+					url,
+					..Default::default()
+				});
+			}
+		}
+		Ok(MangaPageResult { manga, has_more })
+	}
+
+	/// Manga id out of any manga or chapter url,
+	/// {base_url}/{manga_path}/{manga_id}[/{chapter_id}]
+	fn manga_id_from_url(&self, url: &str) -> String {
+		url.substring_after(&format!("/{}/", self.manga_path))
+			.unwrap_or_default()
+			.split('/')
+			.next()
+			.map(String::from)
+			.unwrap_or_default()
+	}
+
+	/// Parse the manga cells of the latest releases page or the homepage's
+	/// hot updates widget
+	fn parse_manga_cells(&self, html: &Node, selector: &str) -> Vec<Manga> {
+		let mut manga: Vec<Manga> = Vec::new();
+		for elem in html.select(selector).array() {
+			if let Ok(node) = elem.as_node() {
+				let link = node
+					.select(format!("a[href*='/{}/']", self.manga_path))
+					.first();
+				let url = link.attr("abs:href").read();
+				let id = self.manga_id_from_url(&url);
+				if id.is_empty() || manga.iter().any(|m: &Manga| m.id == id) {
+					continue;
+				}
+				let mut title = node
+					.select("h3 a, .manga-name a, a.chart-title strong")
+					.first()
+					.text()
+					.read();
+				if title.is_empty() {
+					title = link.text().read();
 				}
+				let cover = self.guess_cover(&node.select("img").attr("abs:src").read(), &id);
+				manga.push(Manga {
+					url: format!("{}/{}/{}", self.base_url, self.manga_path, id),
+					id,
+					cover,
+					title,
+					..Default::default()
+				});
 			}
+		}
+		manga
+	}
+
+	pub fn get_manga_listing(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+		if listing.name == self.listing_latest {
+			let url = format!(
+				"{}/latest-release?page={}",
+				self.base_url,
+				itoa::Buffer::new().format(page)
+			);
+			let html = Request::new(&url, HttpMethod::Get).html()?;
+			decode_cfemail(&html);
+			let manga = self.parse_manga_cells(&html, "div.mangalist div.manga-item");
+			let has_more = !html.select("a[rel=next]").array().is_empty();
 			Ok(MangaPageResult { manga, has_more })
+		} else if listing.name == self.listing_hot {
+			// The widget only lives on the homepage, so there is a single page
+			let html = Request::new(self.base_url, HttpMethod::Get).html()?;
+			decode_cfemail(&html);
+			Ok(MangaPageResult {
+				manga: self.parse_manga_cells(&html, ".hot-thumbnails li"),
+				has_more: false,
+			})
+		} else if listing.name == self.listing_popular {
+			self.parse_filter_list(&format!(
+				"{}/filterList?page={}&sortBy=views&asc=false",
+				self.base_url,
+				itoa::Buffer::new().format(page)
+			))
+		} else {
+			Err(AidokuError {
+				reason: aidoku::error::AidokuErrorKind::Unimplemented,
+			})
 		}
 	}
 
@@ -319,41 +499,44 @@ impl<'a> MMRCMSSource<'a> {
 			..Default::default()
 		};
 
+		let locale = self.locale();
+		let mut extra_info = Vec::new();
 		for elem in html.select(".row .dl-horizontal dt").array() {
 			if let Ok(node) = elem.as_node()
 			   && let Some(next_node) = node.next() {
-				let text = node.text().read().to_lowercase();
-				#[rustfmt::skip]
-				match text.substring_before(':').unwrap_or(&text) {
-					"author(s)" | "autor(es)" | "auteur(s)" | "著作" | "yazar(lar)" | "mangaka(lar)" | "pengarang/penulis" | "pengarang" | "penulis" | "autor" | "المؤلف" | "перевод" | "autor/autorzy" | "автор" => {
-						manga.author = next_node.text().read();
-					}
-					"artist(s)" | "artiste(s)" | "sanatçi(lar)" | "artista(s)" | "artist(s)/ilustrator" | "الرسام" | "seniman" | "rysownik/rysownicy" => { 
-						manga.artist = next_node.text().read()
-					}
-					"categories" | "categorías" | "catégories" | "ジャンル" | "kategoriler" | "categorias" | "kategorie" | "التصنيفات" | "жанр" | "kategori" | "tagi" | "tags" => {
-						manga.categories.extend(next_node
+				let text = node.text().read();
+				let label = text.substring_before(':').unwrap_or(&text).trim();
+				match locale.label(&label.to_lowercase()) {
+					Some(Label::Author) => manga.author = next_node.text().read(),
+					Some(Label::Artist) => manga.artist = next_node.text().read(),
+					Some(Label::Categories) => manga.categories.extend(
+						next_node
 							.select("a")
 							.array()
-							.filter_map(|elem| elem.as_node().map(|node| node.text().read()).ok())
-						)
+							.filter_map(|elem| elem.as_node().map(|node| node.text().read()).ok()),
+					),
+					Some(Label::Status) => {
+						manga.status = locale.status(next_node.text().read().to_lowercase().trim())
 					}
-					"status" | "statut" | "estado" | "状態" | "durum" | "الحالة" | "статус" => {
-						manga.status = match next_node.text().read().to_lowercase().trim() {
-							"complete" | "مكتملة" | "complet" | "completo" | "zakończone" | "concluído" => MangaStatus::Completed,
-							"ongoing" | "مستمرة" | "en cours" | "em lançamento" | "prace w toku" | "ativo" | "em andamento" | "en curso" => MangaStatus::Ongoing,
-							"wstrzymane" => MangaStatus::Hiatus,
-							"porzucone" => MangaStatus::Cancelled,
-							_ => MangaStatus::Unknown,
+					Some(Label::Type) => manga.categories.push(next_node.text().read()),
+					Some(Label::OtherNames) | Some(Label::ReleaseYear) => {
+						let value = next_node.text().read();
+						if !value.is_empty() {
+							extra_info.push(format!("{}: {}", label, value));
 						}
 					}
-					"type" | "ttipo" | "النوع" | "tür" => {
-						manga.categories.push(next_node.text().read())
-					}
-					_ => continue,
+					None => continue,
 				}
 			}
 		}
+		if !extra_info.is_empty() {
+			let extra_info = extra_info.join("\n");
+			manga.description = if manga.description.trim().is_empty() {
+				extra_info
+			} else {
+				format!("{}\n\n{}", manga.description.trim_end(), extra_info)
+			};
+		}
 		manga.categories.sort_unstable();
 		manga.categories.dedup();
 		(manga.nsfw, manga.viewer) = (self.category_parser)(&html, manga.categories.clone());
@@ -375,6 +558,7 @@ impl<'a> MMRCMSSource<'a> {
 			.text()
 			.read();
 		let should_extract_chapter_title = node.select("em").array().is_empty();
+		let locale = self.locale();
 		Ok(elems
 			.filter_map(|elem| {
 				if let Ok(chapter_node) = elem.as_node() {
@@ -393,11 +577,13 @@ impl<'a> MMRCMSSource<'a> {
 							title = chapter_title;
 						}
 
-						let date_updated = chapter_node
-							.select("div.date-chapter-title-rtl, div.col-md-4")
-							.first()
-							.own_text()
-							.as_date("dd MMM'.' yyyy", Some("en_US"), None);
+						let date_updated = locale.parse_date(
+							&chapter_node
+								.select("div.date-chapter-title-rtl, div.col-md-4")
+								.first()
+								.own_text()
+								.read(),
+						);
 
 						Some(Chapter {
 							id: chapter_id,
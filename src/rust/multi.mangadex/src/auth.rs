@@ -0,0 +1,124 @@
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind, Result},
+	std::{
+		defaults::{defaults_get, defaults_set},
+		net::{HttpMethod, Request},
+		String, StringRef, ValueRef,
+	},
+};
+
+use crate::helper::{current_date, urlencode};
+
+const TOKEN_URL: &str = "https://auth.mangadex.org/realms/mangadex/protocol/openid-connect/token";
+
+/// Seconds before expiry at which an access token is refreshed.
+const EXPIRY_MARGIN: f64 = 60.0;
+
+fn get_setting(key: &str) -> String {
+	defaults_get(key)
+		.as_string()
+		.map(|v| v.read())
+		.unwrap_or_default()
+}
+
+fn set_setting(key: &str, value: &str) {
+	defaults_set(key, StringRef::from(value).0);
+}
+
+/// Whether the API client and account settings are all filled in.
+pub fn has_credentials() -> bool {
+	["clientId", "clientSecret", "username", "password"]
+		.iter()
+		.all(|key| !get_setting(key).trim().is_empty())
+}
+
+/// Sends a grant to the token endpoint and stores the tokens it returns.
+fn request_token(grant: String) -> bool {
+	let mut body = grant;
+	body.push_str("&client_id=");
+	body.push_str(&urlencode(String::from(get_setting("clientId").trim())));
+	body.push_str("&client_secret=");
+	body.push_str(&urlencode(String::from(get_setting("clientSecret").trim())));
+
+	let json = Request::new(TOKEN_URL, HttpMethod::Post)
+		.header("Content-Type", "application/x-www-form-urlencoded")
+		.body(body.as_bytes())
+		.json();
+	let json = match json.as_object() {
+		Ok(json) => json,
+		Err(_) => return false,
+	};
+	let access_token = match json.get("access_token").as_string() {
+		Ok(access_token) => access_token.read(),
+		Err(_) => return false,
+	};
+	let expires_in = json.get("expires_in").as_float().unwrap_or(900.0);
+
+	set_setting("accessToken", &access_token);
+	defaults_set(
+		"accessTokenExpiry",
+		ValueRef::from(current_date() + expires_in),
+	);
+	if let Ok(refresh_token) = json.get("refresh_token").as_string() {
+		set_setting("refreshToken", &refresh_token.read());
+	}
+	true
+}
+
+/// Logs in with the account settings, replacing any stored tokens.
+fn login() -> bool {
+	logout();
+	if !has_credentials() {
+		return false;
+	}
+	let mut grant = String::from("grant_type=password&username=");
+	grant.push_str(&urlencode(String::from(get_setting("username").trim())));
+	grant.push_str("&password=");
+	grant.push_str(&urlencode(get_setting("password")));
+	request_token(grant)
+}
+
+fn refresh() -> bool {
+	let refresh_token = get_setting("refreshToken");
+	if refresh_token.is_empty() {
+		return false;
+	}
+	let mut grant = String::from("grant_type=refresh_token&refresh_token=");
+	grant.push_str(&urlencode(refresh_token));
+	request_token(grant)
+}
+
+/// Forgets the stored tokens.
+pub fn logout() {
+	for key in ["accessToken", "refreshToken"] {
+		set_setting(key, "");
+	}
+}
+
+/// A valid access token, refreshed when it expired. Logs in when there's
+/// none, which is only tried once all the account settings are filled in.
+pub fn access_token() -> Option<String> {
+	let token = get_setting("accessToken");
+	let expiry = defaults_get("accessTokenExpiry").as_float().unwrap_or(0.0);
+	if !token.is_empty() && current_date() + EXPIRY_MARGIN < expiry {
+		return Some(token);
+	}
+	if refresh() || login() {
+		let token = get_setting("accessToken");
+		if !token.is_empty() {
+			return Some(token);
+		}
+	}
+	None
+}
+
+/// A request carrying the account's access token, for `/user` endpoints.
+/// Fails when the account settings are missing or the login is refused.
+pub fn authorized_request(url: &str, method: HttpMethod) -> Result<Request> {
+	let token = access_token().ok_or(AidokuError {
+		reason: AidokuErrorKind::DefaultNotFound,
+	})?;
+	let mut authorization = String::from("Bearer ");
+	authorization.push_str(&token);
+	Ok(Request::new(url, method).header("Authorization", &authorization))
+}
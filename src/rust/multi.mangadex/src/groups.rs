@@ -0,0 +1,258 @@
+use aidoku::{
+	std::{
+		defaults::{defaults_get, defaults_set},
+		net::{HttpMethod, Request},
+		ArrayRef, ObjectRef, String, StringRef, ValueRef, Vec,
+	},
+	Chapter,
+};
+
+use alloc::collections::BTreeMap;
+
+use crate::helper::{current_date, urlencode};
+use ratelimit::SendRatelimited;
+
+/// Defaults key of the group name to UUID cache.
+const CACHE_KEY: &str = "groupIdCache";
+
+/// Seconds before names that couldn't be looked up are tried again.
+const RETRY_INTERVAL: f64 = 3.0 * 60.0 * 60.0;
+
+pub fn is_uuid(value: &str) -> bool {
+	value.len() == 36
+		&& value.char_indices().all(|(i, c)| match i {
+			8 | 13 | 18 | 23 => c == '-',
+			_ => c.is_ascii_hexdigit(),
+		})
+}
+
+/// Looks up a scanlation group by its exact name, ignoring case.
+fn find_group_id(name: &str) -> Option<String> {
+	let url = String::from("https://api.mangadex.org/group?limit=10&name=")
+		+ &urlencode(String::from(name));
+	let json = Request::new(&url, HttpMethod::Get)
+		.json_rl()
+		.ok()?
+		.as_object()
+		.ok()?;
+	let name = name.to_lowercase();
+	json.get("data").as_array().ok()?.find_map(|group| {
+		let group = group.as_object().ok()?;
+		let group_name = group
+			.get("attributes")
+			.as_object()
+			.and_then(|attributes| attributes.get("name").as_string())
+			.map(|v| v.read())
+			.ok()?;
+		if group_name.to_lowercase() == name {
+			group.get("id").as_string().map(|id| id.read()).ok()
+		} else {
+			None
+		}
+	})
+}
+
+/// Turns the comma-separated group names or UUIDs of a setting into the
+/// UUIDs of its `…Array` default, keeping the list's order. Names are looked
+/// up once and cached. Names that can't be looked up, because the request
+/// failed or no group has that name yet, are kept in `…Unresolved` and tried
+/// again by `group_ids` once `RETRY_INTERVAL` passed since `…Attempted`.
+pub fn store_group_ids(key: &str) {
+	let list = defaults_get(key)
+		.as_string()
+		.map(|v| v.read())
+		.unwrap_or_default();
+	let mut cache = defaults_get(CACHE_KEY)
+		.as_object()
+		.unwrap_or_else(|_| ObjectRef::new());
+	let mut ids = ArrayRef::new();
+	let mut unresolved = ArrayRef::new();
+	for entry in list.split(',') {
+		let entry = entry.trim();
+		if entry.is_empty() {
+			continue;
+		}
+		if is_uuid(entry) {
+			ids.insert(StringRef::from(entry).0);
+			continue;
+		}
+		let cache_key = entry.to_lowercase();
+		if let Ok(id) = cache.get(&cache_key).as_string() {
+			ids.insert(id.0);
+		} else if let Some(id) = find_group_id(entry) {
+			cache.set(&cache_key, StringRef::from(id.as_str()).0);
+			ids.insert(StringRef::from(id.as_str()).0);
+		} else {
+			unresolved.insert(StringRef::from(entry).0);
+		}
+	}
+	defaults_set(CACHE_KEY, cache.0);
+	defaults_set(&(String::from(key) + "Array"), ids.0);
+	defaults_set(&(String::from(key) + "Unresolved"), unresolved.0);
+	defaults_set(
+		&(String::from(key) + "Attempted"),
+		ValueRef::from(current_date()),
+	);
+}
+
+/// Whether names left unresolved at `attempted` are looked up again.
+fn should_retry(pending: bool, attempted: f64, now: f64) -> bool {
+	pending && now - attempted >= RETRY_INTERVAL
+}
+
+/// UUIDs of a group setting, looking up the names left unresolved first
+/// when they weren't tried recently.
+pub fn group_ids(key: &str) -> Vec<String> {
+	let pending = defaults_get(&(String::from(key) + "Unresolved"))
+		.as_array()
+		.map(|names| names.len() > 0)
+		.unwrap_or(false);
+	let attempted = defaults_get(&(String::from(key) + "Attempted"))
+		.as_float()
+		.unwrap_or(0.0);
+	if should_retry(pending, attempted, current_date()) {
+		store_group_ids(key);
+	}
+	defaults_get(&(String::from(key) + "Array"))
+		.as_array()
+		.map(|ids| {
+			ids.filter_map(|id| id.as_string().map(|v| v.read()).ok())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default()
+}
+
+/// Rank of a chapter's groups in the preferred list, lower is better.
+fn rank(groups: &[String], preferred: &[String]) -> usize {
+	groups
+		.iter()
+		.filter_map(|group| preferred.iter().position(|id| id == group))
+		.min()
+		.unwrap_or(preferred.len())
+}
+
+/// Keeps one upload per chapter number and language, the one of the
+/// best-ranked group, or the first one listed when none is preferred.
+/// Chapters without a number, like oneshots, are all kept.
+pub fn dedupe_chapters(
+	chapters: Vec<(Chapter, Vec<String>)>,
+	preferred: &[String],
+) -> Vec<Chapter> {
+	let mut kept: Vec<(Chapter, usize)> = Vec::with_capacity(chapters.len());
+	// index in `kept` of each chapter number and language
+	let mut numbered: BTreeMap<(u32, String), usize> = BTreeMap::new();
+	for (chapter, groups) in chapters {
+		let chapter_rank = rank(&groups, preferred);
+		if chapter.chapter < 0.0 {
+			kept.push((chapter, chapter_rank));
+			continue;
+		}
+		let key = (chapter.chapter.to_bits(), chapter.lang.clone());
+		match numbered.get(&key) {
+			Some(&index) => {
+				if chapter_rank < kept[index].1 {
+					kept[index] = (chapter, chapter_rank);
+				}
+			}
+			None => {
+				numbered.insert(key, kept.len());
+				kept.push((chapter, chapter_rank));
+			}
+		}
+	}
+	kept.into_iter().map(|(chapter, _)| chapter).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chapter(id: &str, number: f32, lang: &str) -> Chapter {
+		Chapter {
+			id: String::from(id),
+			title: String::new(),
+			volume: -1.0,
+			chapter: number,
+			date_updated: 0.0,
+			scanlator: String::new(),
+			url: String::new(),
+			lang: String::from(lang),
+		}
+	}
+
+	fn ids(ids: &[&str]) -> Vec<String> {
+		ids.iter().map(|id| String::from(*id)).collect()
+	}
+
+	fn dedupe(chapters: &[(&str, f32, &str, &[&str])], preferred: &[&str]) -> Vec<String> {
+		let chapters = chapters
+			.iter()
+			.map(|(id, number, lang, groups)| (chapter(id, *number, lang), ids(groups)))
+			.collect::<Vec<_>>();
+		dedupe_chapters(chapters, &ids(preferred))
+			.into_iter()
+			.map(|chapter| chapter.id)
+			.collect()
+	}
+
+	#[test]
+	fn recognizes_uuids() {
+		assert!(is_uuid("5fed0576-8b94-4f9a-b6a7-08eecd69800d"));
+		assert!(is_uuid("5FED0576-8B94-4F9A-B6A7-08EECD69800D"));
+		assert!(!is_uuid("5fed0576-8b94-4f9a-b6a7-08eecd69800"));
+		assert!(!is_uuid("5fed0576x8b94-4f9a-b6a7-08eecd69800d"));
+		assert!(!is_uuid("5fed0576-8b94-4f9a-b6a7-08eecd69800g"));
+		assert!(!is_uuid("MangaPlus"));
+	}
+
+	#[test]
+	fn retries_unresolved_names_every_few_hours() {
+		let now = 1_700_000_000.0;
+		assert!(should_retry(true, 0.0, now));
+		assert!(should_retry(true, now - RETRY_INTERVAL, now));
+		assert!(!should_retry(true, now - 60.0, now));
+		assert!(!should_retry(false, 0.0, now));
+	}
+
+	#[test]
+	fn ranks_by_the_best_preferred_group() {
+		let preferred = ids(&["a", "b", "c"]);
+		assert_eq!(rank(&ids(&["c", "b"]), &preferred), 1);
+		assert_eq!(rank(&ids(&["a"]), &preferred), 0);
+		assert_eq!(rank(&ids(&["x"]), &preferred), 3);
+		assert_eq!(rank(&[], &preferred), 3);
+		assert_eq!(rank(&ids(&["a"]), &[]), 0);
+	}
+
+	#[test]
+	fn keeps_the_first_upload_without_preferences() {
+		let chapters: [(&str, f32, &str, &[&str]); 3] = [
+			("1", 2.0, "en", &["a"]),
+			("2", 2.0, "en", &["b"]),
+			("3", 1.0, "en", &["b"]),
+		];
+		assert_eq!(dedupe(&chapters, &[]), ["1", "3"]);
+	}
+
+	#[test]
+	fn keeps_the_preferred_upload_in_place() {
+		let chapters: [(&str, f32, &str, &[&str]); 4] = [
+			("1", 2.0, "en", &["a"]),
+			("2", 1.0, "en", &["a"]),
+			("3", 2.0, "en", &["b"]),
+			("4", 1.0, "en", &["c"]),
+		];
+		assert_eq!(dedupe(&chapters, &["c", "b"]), ["3", "4"]);
+	}
+
+	#[test]
+	fn keeps_each_language_and_unnumbered_chapters() {
+		let chapters: [(&str, f32, &str, &[&str]); 4] = [
+			("1", 1.0, "en", &["a"]),
+			("2", 1.0, "fr", &["a"]),
+			("3", -1.0, "en", &["a"]),
+			("4", -1.0, "en", &["b"]),
+		];
+		assert_eq!(dedupe(&chapters, &[]), ["1", "2", "3", "4"]);
+	}
+}
@@ -1,6 +1,8 @@
 #![no_std]
 #![feature(let_chains)]
 extern crate alloc;
+mod auth;
+mod groups;
 mod helper;
 mod parser;
 use aidoku::{
@@ -15,20 +17,14 @@ use aidoku::{
 };
 use alloc::borrow::ToOwned;
 use helper::*;
-
-#[link(wasm_import_module = "net")]
-extern "C" {
-	fn set_rate_limit(rate_limit: i32);
-	fn set_rate_limit_period(period: i32);
-}
+use ratelimit::{RateLimit, SendRatelimited};
 
 #[no_mangle]
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "C" fn initialize() {
-	set_rate_limit(3);
-	set_rate_limit_period(1);
+	RateLimit::DEFAULT.install();
 
-	for key in ["blockedGroups", "blockedUploaders"] {
+	for key in ["blockedGroups", "blockedUploaders", "preferredGroups", "mdLists"] {
 		let arrkey = key.to_owned() + "Array";
 		if defaults_get(&arrkey).as_array().is_err() {
 			handle_notification(String::from(key));
@@ -147,7 +143,7 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 		}
 	}
 
-	let json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+	let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 
 	let data = json.get("data").as_array()?;
 
@@ -191,92 +187,234 @@ fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
 			&limit=40\
 			&offset=",
 		) + itoa::Buffer::new().format(offset);
-		if let Ok(languages) = defaults_get("languages").as_array() {
-			languages.for_each(|lang| {
-				if let Ok(lang) = lang.as_string() {
-					url.push_str("&translatedLanguage[]=");
-					url.push_str(&lang.read());
-				}
-			})
-		}
-		if let Ok(groups) = defaults_get("blockedGroupsArray").as_array() {
-			groups.for_each(|group| {
-				if let Ok(group) = group.as_string() {
-					url.push_str("&excludedGroups[]=");
-					url.push_str(&group.read());
-				}
-			});
-		}
-		if let Ok(groups) = defaults_get("blockedUploadersArray").as_array() {
-			groups.for_each(|group| {
-				if let Ok(group) = group.as_string() {
-					url.push_str("&excludedUploaders[]=");
-					url.push_str(&group.read());
-				}
-			});
-		}
+		push_chapter_filters(&mut url);
 
-		let mut json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+		let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 
 		let total = json.get("total").as_int().unwrap_or(0) as i32;
-		let mut data = json.get("data").as_array()?;
-
-		let manga_ids = data
-			.filter_map(|chapter| match chapter.as_object() {
-				Ok(obj) => {
-					if let Ok(relationships) = obj.get("relationships").as_array() {
-						for relationship in relationships {
-							if let Ok(relationship) = relationship.as_object()
-								   && let Ok(relation_type) = relationship.get("type").as_string() 
-								   && relation_type.read() == "manga"
-								   && let Ok(id) = relationship.get("id").as_string() {
-									let mut ret = String::from("&ids[]=");
-									ret.push_str(&id.read());
-									return Some(ret);
-								}
-						}
-						None
-					} else {
-						None
-					}
-				}
-				Err(_) => None,
-			})
-			.collect::<String>();
-
-		url = String::from(
-			"https://api.mangadex.org/manga\
-			?includes[]=cover_art\
-			&order[updatedAt]=desc\
+		let manga = manga_from_chapters(json.get("data").as_array()?, false)?;
+
+		return Ok(MangaPageResult {
+			manga,
+			has_more: offset + 20 < total,
+		});
+	} else if listing.name == "Followed updates" {
+		// recently published chapters of followed manga
+		let offset = (page - 1) * 40;
+		let mut url = String::from(
+			"https://api.mangadex.org/user/follows/manga/feed\
+			?includes[]=manga\
+			&order[publishAt]=desc\
+			&includeFutureUpdates=0\
+			&contentRating[]=pornographic\
 			&contentRating[]=erotica\
 			&contentRating[]=suggestive\
-			&contentRating[]=safe",
-		) + &manga_ids;
-		json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
-		data = json.get("data").as_array()?;
-		let manga = data
-			.filter_map(|manga| match manga.as_object() {
-				Ok(obj) => parser::parse_basic_manga(obj).ok(),
-				Err(_) => None,
-			})
-			.collect::<Vec<_>>();
+			&contentRating[]=safe\
+			&limit=40\
+			&offset=",
+		) + itoa::Buffer::new().format(offset);
+		push_chapter_filters(&mut url);
+
+		let json = auth::authorized_request(&url, HttpMethod::Get)?
+			.json_rl()?
+			.as_object()?;
+
+		let total = json.get("total").as_int().unwrap_or(0) as i32;
+		let manga = manga_from_chapters(json.get("data").as_array()?, true)?;
 
 		return Ok(MangaPageResult {
 			manga,
-			has_more: offset + 20 < total,
+			has_more: offset + 40 < total,
 		});
+	} else if listing.name == "MDLists" {
+		return get_list_listing(page);
+	} else if let Some(status) = match listing.name.as_str() {
+		"Reading" => Some("reading"),
+		"Plan to read" => Some("plan_to_read"),
+		"Completed" => Some("completed"),
+		_ => None,
+	} {
+		return get_status_listing(status, page);
 	}
 
 	get_manga_list(filters, page)
 }
 
+// Adds the language and blocked group settings to a chapter feed request
+fn push_chapter_filters(url: &mut String) {
+	if let Ok(languages) = defaults_get("languages").as_array() {
+		languages.for_each(|lang| {
+			if let Ok(lang) = lang.as_string() {
+				url.push_str("&translatedLanguage[]=");
+				url.push_str(&lang.read());
+			}
+		})
+	}
+	for group in groups::group_ids("blockedGroups") {
+		url.push_str("&excludedGroups[]=");
+		url.push_str(&group);
+	}
+	if let Ok(groups) = defaults_get("blockedUploadersArray").as_array() {
+		groups.for_each(|group| {
+			if let Ok(group) = group.as_string() {
+				url.push_str("&excludedUploaders[]=");
+				url.push_str(&group.read());
+			}
+		});
+	}
+}
+
+// Fetches the manga of a chapter feed page, keeping the feed's order
+fn manga_from_chapters(data: ArrayRef, pornographic: bool) -> Result<Vec<Manga>> {
+	let mut manga_ids: Vec<String> = Vec::new();
+	for chapter in data {
+		if let Ok(obj) = chapter.as_object()
+			&& let Ok(relationships) = obj.get("relationships").as_array()
+		{
+			for relationship in relationships {
+				if let Ok(relationship) = relationship.as_object()
+					&& let Ok(relation_type) = relationship.get("type").as_string()
+					&& relation_type.read() == "manga"
+					&& let Ok(id) = relationship.get("id").as_string()
+				{
+					let id = id.read();
+					if !manga_ids.contains(&id) {
+						manga_ids.push(id);
+					}
+					break;
+				}
+			}
+		}
+	}
+	get_manga_by_ids(&manga_ids, pornographic)
+}
+
+// Fetches manga with their covers, in the order of `ids`
+fn get_manga_by_ids(ids: &[String], pornographic: bool) -> Result<Vec<Manga>> {
+	if ids.is_empty() {
+		return Ok(Vec::new());
+	}
+	let mut url = String::from(
+		"https://api.mangadex.org/manga\
+		?includes[]=cover_art\
+		&contentRating[]=erotica\
+		&contentRating[]=suggestive\
+		&contentRating[]=safe\
+		&limit=100",
+	);
+	if pornographic {
+		url.push_str("&contentRating[]=pornographic");
+	}
+	for id in ids {
+		url.push_str("&ids[]=");
+		url.push_str(id);
+	}
+	let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
+	let mut manga = json
+		.get("data")
+		.as_array()?
+		.filter_map(|manga| match manga.as_object() {
+			Ok(obj) => parser::parse_basic_manga(obj).ok(),
+			Err(_) => None,
+		})
+		.collect::<Vec<_>>();
+	manga.sort_by_key(|manga| ids.iter().position(|id| *id == manga.id));
+	Ok(manga)
+}
+
+// Manga ids of a custom list
+fn list_manga_ids(list_id: &str) -> Result<Vec<String>> {
+	let url = String::from("https://api.mangadex.org/list/") + list_id;
+	// private lists are only visible to their owner
+	let request = if auth::has_credentials()
+		&& let Ok(request) = auth::authorized_request(&url, HttpMethod::Get)
+	{
+		request
+	} else {
+		Request::new(&url, HttpMethod::Get)
+	};
+	let json = request.json_rl()?.as_object()?;
+	Ok(json
+		.get("data")
+		.as_object()?
+		.get("relationships")
+		.as_array()?
+		.filter_map(|relationship| {
+			if let Ok(relationship) = relationship.as_object()
+				&& let Ok(relation_type) = relationship.get("type").as_string()
+				&& relation_type.read() == "manga"
+				&& let Ok(id) = relationship.get("id").as_string()
+			{
+				Some(id.read())
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>())
+}
+
+// Manga of every custom list from the settings, in the order the lists were
+// entered, 20 per page
+fn get_list_listing(page: i32) -> Result<MangaPageResult> {
+	let mut ids: Vec<String> = Vec::new();
+	if let Ok(lists) = defaults_get("mdListsArray").as_array() {
+		for list in lists {
+			for id in list_manga_ids(&list.as_string()?.read())? {
+				if !ids.contains(&id) {
+					ids.push(id);
+				}
+			}
+		}
+	}
+
+	let offset = ((page - 1) * 20) as usize;
+	let end = ids.len().min(offset + 20);
+	if offset >= end {
+		return Ok(MangaPageResult {
+			manga: Vec::new(),
+			has_more: false,
+		});
+	}
+	Ok(MangaPageResult {
+		manga: get_manga_by_ids(&ids[offset..end], true)?,
+		has_more: end < ids.len(),
+	})
+}
+
+// Manga of the account's library with a reading status, 20 per page
+fn get_status_listing(status: &str, page: i32) -> Result<MangaPageResult> {
+	let url = String::from("https://api.mangadex.org/manga/status?status=") + status;
+	let json = auth::authorized_request(&url, HttpMethod::Get)?
+		.json_rl()?
+		.as_object()?;
+	let ids = json
+		.get("statuses")
+		.as_object()?
+		.keys()
+		.filter_map(|id| id.as_string().map(|id| id.read()).ok())
+		.collect::<Vec<_>>();
+
+	let offset = ((page - 1) * 20) as usize;
+	let end = ids.len().min(offset + 20);
+	if offset >= end {
+		return Ok(MangaPageResult {
+			manga: Vec::new(),
+			has_more: false,
+		});
+	}
+	Ok(MangaPageResult {
+		manga: get_manga_by_ids(&ids[offset..end], true)?,
+		has_more: end < ids.len(),
+	})
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	let url = String::from("https://api.mangadex.org/manga/")
 		+ &id + "?includes[]=cover_art\
 		&includes[]=author\
 		&includes[]=artist";
-	let json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+	let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 
 	let data = json.get("data").as_object()?;
 
@@ -295,43 +433,13 @@ fn get_chapter_list(id: String) -> Result<Vec<Chapter>> {
 		&contentRating[]=suggestive\
 		&contentRating[]=safe\
 		&includes[]=scanlation_group";
-
-	if let Ok(languages) = defaults_get("languages").as_array() {
-		languages.for_each(|lang| {
-			if let Ok(lang) = lang.as_string() {
-				url.push_str("&translatedLanguage[]=");
-				url.push_str(&lang.read());
-			}
-		})
-	}
-	if let Ok(groups) = defaults_get("blockedGroupsArray").as_array() {
-		groups.for_each(|group| {
-			if let Ok(group) = group.as_string() {
-				url.push_str("&excludedGroups[]=");
-				url.push_str(&group.read());
-			}
-		});
-	}
-	if let Ok(groups) = defaults_get("blockedUploadersArray").as_array() {
-		groups.for_each(|group| {
-			if let Ok(group) = group.as_string() {
-				url.push_str("&excludedUploaders[]=");
-				url.push_str(&group.read());
-			}
-		});
-	}
-	let json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+	push_chapter_filters(&mut url);
+	let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 	let total = json.get("total").as_int().unwrap_or(0);
 	let data = json.get("data").as_array()?;
-	let mut chapters: Vec<Chapter> = Vec::with_capacity(total.try_into().unwrap_or(0));
-	chapters.append(
-		&mut data
-			.filter_map(|chapter| match chapter.as_object() {
-				Ok(obj) => parser::parse_chapter(obj).ok(),
-				Err(_) => None,
-			})
-			.collect::<Vec<_>>(),
-	);
+	let mut chapters: Vec<(Chapter, Vec<String>)> =
+		Vec::with_capacity(total.try_into().unwrap_or(0));
+	chapters.append(&mut parse_chapters_with_groups(data));
 
 	let mut offset = 500;
 	while offset < total {
@@ -339,22 +447,36 @@ fn get_chapter_list(id: String) -> Result<Vec<Chapter>> {
 			&(url.clone() + "&offset=" + itoa::Buffer::new().format(offset)),
 			HttpMethod::Get,
 		)
-		.json_rl();
+		.json_rl()?;
 
 		if let Ok(json) = json.as_object() {
 			let data = json.get("data").as_array()?;
-			chapters.append(
-				&mut data
-					.filter_map(|chapter| match chapter.as_object() {
-						Ok(obj) => parser::parse_chapter(obj).ok(),
-						Err(_) => None,
-					})
-					.collect::<Vec<_>>(),
-			);
+			chapters.append(&mut parse_chapters_with_groups(data));
 		}
 		offset += 500;
 	}
-	Ok(chapters)
+
+	if defaults_get("oneChapterPerNumber").as_bool().unwrap_or(false) {
+		let preferred = groups::group_ids("preferredGroups");
+		Ok(groups::dedupe_chapters(chapters, &preferred))
+	} else {
+		Ok(chapters.into_iter().map(|(chapter, _)| chapter).collect())
+	}
+}
+
+// Parses a feed page, keeping the scanlation group ids of each chapter
+fn parse_chapters_with_groups(data: ArrayRef) -> Vec<(Chapter, Vec<String>)> {
+	data.filter_map(|chapter| match chapter.as_object() {
+		Ok(obj) => {
+			let groups = parser::parse_chapter_groups(&obj)
+				.into_iter()
+				.map(|group| group.id)
+				.collect::<Vec<_>>();
+			parser::parse_chapter(obj).ok().map(|chapter| (chapter, groups))
+		}
+		Err(_) => None,
+	})
+	.collect::<Vec<_>>()
 }
 
 #[get_page_list]
@@ -363,7 +485,7 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 	if defaults_get("standardHttpsPort").as_bool().unwrap_or(false) {
 		url.push_str("?forcePort443=true");
 	}
-	let json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+	let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 
 	let chapter = json.get("chapter").as_object()?;
 	let data = chapter
@@ -410,11 +532,14 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 
 #[handle_url]
 pub fn handle_url(url: String) -> Result<DeepLink> {
-	let url = &url[21..]; // remove "https://mangadex.org/"
+	// remove "https://mangadex.org/"
+	let url = match url.find("mangadex.org/") {
+		Some(index) => &url[index + 13..],
+		None => url.as_str(),
+	};
 
-	if url.starts_with("title") {
+	if let Some(id) = url.strip_prefix("title/") {
 		// ex: https://mangadex.org/title/a96676e5-8ae2-425e-b549-7f15dd34a6d8/komi-san-wa-komyushou-desu
-		let id = &url[6..]; // remove "title/"
 		let end = id.find('/').unwrap_or(id.len());
 		let manga_id = &id[..end];
 
@@ -422,15 +547,14 @@ pub fn handle_url(url: String) -> Result<DeepLink> {
 			manga: get_manga_details(String::from(manga_id)).ok(),
 			chapter: None,
 		});
-	} else if url.starts_with("chapter") {
+	} else if let Some(id) = url.strip_prefix("chapter/") {
 		// ex: https://mangadex.org/chapter/56eecc6f-1a4e-464c-b6a4-a1cbdfdfd726/1
-		let id = &url[8..]; // remove "chapter/"
 		let end = id.find('/').unwrap_or(id.len());
 		let chapter_id = &id[..end];
 
 		let url = String::from("https://api.mangadex.org/chapter/") + chapter_id;
 
-		let json = Request::new(&url, HttpMethod::Get).json_rl().as_object()?;
+		let json = Request::new(&url, HttpMethod::Get).json_rl()?.as_object()?;
 
 		let chapter_obj = json.get("data").as_object()?;
 		let relationships = chapter_obj.get("relationships").as_array()?;
@@ -456,7 +580,11 @@ pub fn handle_url(url: String) -> Result<DeepLink> {
 #[handle_notification]
 fn handle_notification(notification: String) {
 	match notification.as_str() {
-		"blockedGroups" | "blockedUploaders" => {
+		"blockedGroups" | "preferredGroups" => {
+			// group names are looked up, UUIDs are kept as is
+			groups::store_group_ids(&notification);
+		}
+		"blockedUploaders" | "mdLists" => {
 			if let Ok(groups_string) = defaults_get(&notification).as_string() {
 				let mut arr = ArrayRef::new();
 				groups_string.read().split(',').for_each(|group| {
@@ -468,6 +596,10 @@ fn handle_notification(notification: String) {
 				defaults_set((notification + "Array").as_str(), arr.0);
 			}
 		}
+		"credentials" => {
+			// tokens of the previous account, the next request logs in again
+			auth::logout();
+		}
 		_ => {}
 	}
 }
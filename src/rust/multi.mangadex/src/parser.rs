@@ -222,6 +222,36 @@ pub fn parse_full_manga(manga_object: ObjectRef) -> Result<Manga> {
 	})
 }
 
+pub struct ScanlationGroup {
+	pub id: String,
+	pub name: String,
+}
+
+// Parse every scanlation group credited for a chapter
+pub fn parse_chapter_groups(chapter_object: &ObjectRef) -> Vec<ScanlationGroup> {
+	let mut groups: Vec<ScanlationGroup> = Vec::new();
+
+	if let Ok(relationships) = chapter_object.get("relationships").as_array() {
+		for relationship in relationships {
+			if let Ok(relationship_object) = relationship.as_object()
+				&& let Ok(relation_type) = relationship_object.get("type").as_string()
+				&& relation_type.read() == "scanlation_group"
+				&& let Ok(id) = relationship_object.get("id").as_string()
+			{
+				let name = relationship_object
+					.get("attributes")
+					.as_object()
+					.and_then(|attribs| attribs.get("name").as_string())
+					.map(|v| v.read())
+					.unwrap_or_default();
+				groups.push(ScanlationGroup { id: id.read(), name });
+			}
+		}
+	}
+
+	groups
+}
+
 // Parse chapter info
 pub fn parse_chapter(chapter_object: ObjectRef) -> Result<Chapter> {
 	let attributes = chapter_object.get("attributes").as_object()?;
@@ -249,20 +279,12 @@ pub fn parse_chapter(chapter_object: ObjectRef) -> Result<Chapter> {
 	let volume = attributes.get("volume").as_float().unwrap_or(-1.0) as f32;
 	let chapter = attributes.get("chapter").as_float().unwrap_or(-1.0) as f32;
 
-	let mut scanlator = String::new();
-
-	if let Ok(relationships) = chapter_object.get("relationships").as_array() {
-		for relationship in relationships {
-			if let Ok(relationship_object) = relationship.as_object()
-				&& let Ok(relation_type) = relationship_object.get("type").as_string()
-				&& let Ok(attribs) = relationship_object.get("attributes").as_object()
-				&& relation_type.read() == "scanlation_group"
-			{
-				scanlator = attribs.get("name").as_string().map(|v| v.read()).unwrap_or_default();
-				break;
-			}
-		}
-	}
+	let scanlator = parse_chapter_groups(&chapter_object)
+		.into_iter()
+		.map(|group| group.name)
+		.filter(|name| !name.is_empty())
+		.collect::<Vec<_>>()
+		.join(", ");
 
 	let mut url = String::from("https://mangadex.org/chapter/");
 	url.push_str(&id);
@@ -1,6 +1,4 @@
 #![no_std]
-extern crate alloc;
-use alloc::vec;
 use aidoku::{
 	error::Result,
 	prelude::*,
@@ -13,6 +11,11 @@ static INSTANCE: MangaChanSource = MangaChanSource {
 	base_url: "https://y.hentaichan.live",
 	vol_chap_parser: |_, _| (-1.0, -1.0),
 	author_selector: "div.row:contains(Автор) div.item2 a",
+	related_selector: "div.related h2 a",
+	listing_random: "Случайная",
+	listing_popular: "Популярная",
+	listing_top_rated: "Лучшая",
+	listing_latest: "Последние обновления",
 };
 
 #[get_manga_list]
@@ -40,18 +43,18 @@ fn get_chapter_list(id: String) -> Result<Vec<Chapter>> {
 		.0
 		.as_date("dd MMMM yyyy", Some("ru_RU"), None)
 		.unwrap_or(-1.0);
-	Ok(vec![
-		Chapter {
-			id: html.select("a:contains(Читать онлайн)").attr("href").read(),
-			title: String::new(),
-			volume: -1.0,
-			chapter: 1.0,
-			date_updated,
-			scanlator: String::new(),
-			url: html.select("a:contains(Читать онлайн)").attr("href").read(),
-			lang: String::from("ru"),
-		}
-	])
+	let url = html.select("a:contains(Читать онлайн)").attr("href").read();
+	let chapter = Chapter {
+		id: url.clone(),
+		title: String::new(),
+		volume: -1.0,
+		chapter: 1.0,
+		date_updated,
+		scanlator: String::new(),
+		url,
+		lang: String::from("ru"),
+	};
+	Ok(INSTANCE.get_gallery_chapter_list(id, chapter))
 }
 
 #[get_page_list]
@@ -68,3 +71,8 @@ fn modify_image_request(request: Request) {
 fn handle_url(url: String) -> Result<DeepLink> {
 	INSTANCE.handle_url(url)
 }
+
+#[handle_notification]
+fn handle_notification(notification: String) {
+	INSTANCE.handle_notification(notification)
+}
@@ -21,6 +21,11 @@ static INSTANCE: MangaChanSource = MangaChanSource {
 		}
 	},
 	author_selector: "table.mangatitle tr:contains(Автор) span.translation a",
+	related_selector: "",
+	listing_random: "Случайная",
+	listing_popular: "Популярная",
+	listing_top_rated: "Лучшая",
+	listing_latest: "Последние обновления",
 };
 
 #[get_manga_list]
@@ -57,3 +62,8 @@ fn modify_image_request(request: Request) {
 fn handle_url(url: String) -> Result<DeepLink> {
 	INSTANCE.handle_url(url)
 }
+
+#[handle_notification]
+fn handle_notification(notification: String) {
+	INSTANCE.handle_notification(notification)
+}
@@ -20,6 +20,37 @@ pub fn extract_f32_from_string(title: String, text: String) -> Vec<f32> {
 		.collect::<Vec<f32>>()
 }
 
+/// Part number of a multi-part gallery, the number following "часть" or
+/// "part", e.g. 2 for "Title (часть 2)" or "Title - Part 2". Other numbers
+/// in the title, like a year, are not part numbers.
+pub fn part_number(title: &str) -> Option<f32> {
+	let lower = title.to_lowercase();
+	["часть", "part"]
+		.iter()
+		.filter_map(|marker| lower.rfind(marker).map(|at| &lower[at + marker.len()..]))
+		.find_map(|rest| {
+			let rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '.' || c == ':');
+			let end = rest
+				.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+				.unwrap_or(rest.len());
+			rest[..end].trim_end_matches('.').parse::<f32>().ok()
+		})
+}
+
+/// Path of a link on `base_url`, links to other hosts are kept whole.
+pub fn link_path(base_url: &str, href: &str) -> String {
+	let host = base_url
+		.split_once("://")
+		.map(|(_, host)| host)
+		.unwrap_or(base_url);
+	["https://", "http://", "//"]
+		.iter()
+		.find_map(|scheme| href.strip_prefix(scheme)?.strip_prefix(host))
+		.filter(|path| path.is_empty() || path.starts_with('/'))
+		.map(String::from)
+		.unwrap_or_else(|| String::from(href))
+}
+
 pub fn urlencode(string: String) -> String {
 	let mut result: Vec<u8> = Vec::with_capacity(string.len() * 3);
 	let hex = "0123456789ABCDEF".as_bytes();
@@ -39,6 +70,138 @@ pub fn urlencode(string: String) -> String {
 	String::from_utf8(result).unwrap_or_default()
 }
 
+/// Whether the pagination block links to a page after `page`.
+///
+/// Listings paginate with `?offset=` links and search results with
+/// `list_submit(n)` buttons, but both label their links with the page
+/// number, so the text is all that is needed.
+pub fn has_next_page(html: &Node, page: i32) -> bool {
+	html.select("#pagination a, div.navigation a")
+		.array()
+		.any(|elem| {
+			let text = elem.as_node().text().read();
+			let text = text.trim();
+			text.contains("Вперед")
+				|| text.contains("Далее")
+				|| text.parse::<i32>().map(|n| n > page).unwrap_or(false)
+		})
+}
+
+/// Whether the site served its "registered users only" stub instead of the page
+pub fn is_login_stub(html: &str) -> bool {
+	html.contains("только для зарегистрированных")
+}
+
+/// Value of the cookie `name` in a Set-Cookie header, which joins
+/// multiple cookies with commas
+pub fn cookie_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+	header.split(|c| c == ';' || c == ',').find_map(|part| {
+		let (key, value) = part.trim().split_once('=')?;
+		if key == name {
+			Some(value.trim())
+		} else {
+			None
+		}
+	})
+}
+
+/// Text of the object literal assigned to `name` in a script, e.g. the
+/// `{...}` of `var data = {...};`, found by matching braces outside strings
+pub fn extract_object_literal<'a>(html: &'a str, name: &str) -> Option<&'a str> {
+	let mut search_from = 0;
+	let start = loop {
+		let at = search_from + html[search_from..].find(name)?;
+		search_from = at + name.len();
+		let preceded_by_identifier = html[..at]
+			.chars()
+			.next_back()
+			.map(|c| c.is_alphanumeric() || c == '_' || c == '$')
+			.unwrap_or(false);
+		if preceded_by_identifier {
+			continue;
+		}
+		let rest = html[search_from..].trim_start();
+		if let Some(rest) = rest.strip_prefix('=') {
+			let rest = rest.trim_start();
+			if rest.starts_with('{') {
+				break html.len() - rest.len();
+			}
+		}
+	};
+	let mut depth = 0;
+	let mut quote = None;
+	let mut escaped = false;
+	for (i, c) in html[start..].char_indices() {
+		if let Some(q) = quote {
+			if escaped {
+				escaped = false;
+			} else if c == '\\' {
+				escaped = true;
+			} else if c == q {
+				quote = None;
+			}
+			continue;
+		}
+		match c {
+			'"' | '\'' => quote = Some(c),
+			'{' => depth += 1,
+			'}' => {
+				depth -= 1;
+				if depth == 0 {
+					return Some(&html[start..start + i + 1]);
+				}
+			}
+			_ => {}
+		}
+	}
+	None
+}
+
+/// Turn a JavaScript object literal into JSON by re-quoting single quoted
+/// strings and dropping trailing commas
+pub fn js_object_to_json(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+	let mut quote = None;
+	let mut escaped = false;
+	for c in text.chars() {
+		if let Some(q) = quote {
+			if escaped {
+				escaped = false;
+				if q == '\'' && c == '\'' {
+					result.pop();
+				}
+				result.push(c);
+			} else if c == '\\' {
+				escaped = true;
+				result.push(c);
+			} else if c == q {
+				quote = None;
+				result.push('"');
+			} else if c == '"' {
+				result.push_str("\\\"");
+			} else {
+				result.push(c);
+			}
+			continue;
+		}
+		match c {
+			'"' | '\'' => {
+				quote = Some(c);
+				result.push('"');
+			}
+			']' | '}' => {
+				let trimmed = result.trim_end().len();
+				if result[..trimmed].ends_with(',') {
+					result.truncate(trimmed - 1);
+				}
+				result.push(c);
+			}
+			_ => result.push(c),
+		}
+	}
+	result
+}
+
 pub fn text_with_newlines(node: Node) -> String {
 	let html = node.html().read();
 	if !String::from(html.trim()).is_empty() {
@@ -2,15 +2,19 @@ use aidoku::{
 	error::{AidokuError, Result},
 	prelude::*,
 	std::{
+		defaults::{defaults_get, defaults_set},
 		html::Node,
 		json,
 		net::{HttpMethod, Request},
-		String, Vec,
+		ObjectRef, String, StringRef, Vec,
 	},
 	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaPageResult, MangaStatus, Page,
 };
 
-use crate::helper::{category_parser, text_with_newlines, urlencode};
+use crate::helper::{
+	category_parser, cookie_value, extract_object_literal, has_next_page, is_login_stub,
+	js_object_to_json, link_path, part_number, text_with_newlines, urlencode,
+};
 
 pub static mut CACHED_MANGA_URL: Option<String> = None;
 pub static mut CACHED_MANGA: Option<Vec<u8>> = None;
@@ -22,7 +26,84 @@ pub fn cache_manga_page(url: &str) {
 		}
 
 		CACHED_MANGA_URL = Some(String::from(url));
-		CACHED_MANGA = Some(Request::new(url, HttpMethod::Get).data());
+		CACHED_MANGA = Some(session_request(url, HttpMethod::Get).data());
+	}
+}
+
+/// DLE session cookies saved by `MangaChanSource::login`
+const SESSION_COOKIES: [&str; 2] = ["dle_user_id", "dle_password"];
+
+/// Page shown in place of a chapter only registered users can read
+const LOGIN_REQUIRED: &str = "Эта глава доступна только зарегистрированным пользователям. \
+	Укажите логин и пароль в настройках источника.";
+
+/// Cookie header for the stored session, if logged in
+pub fn session_cookie() -> Option<String> {
+	let cookies = SESSION_COOKIES
+		.iter()
+		.filter_map(|name| {
+			let value = defaults_get(name).as_string().ok()?.read();
+			if value.is_empty() {
+				None
+			} else {
+				Some(format!("{name}={value}"))
+			}
+		})
+		.collect::<Vec<_>>();
+	if cookies.len() == SESSION_COOKIES.len() {
+		Some(cookies.join("; "))
+	} else {
+		None
+	}
+}
+
+/// Request carrying the stored session cookies, if any
+pub fn session_request(url: &str, method: HttpMethod) -> Request {
+	let request = Request::new(url, method);
+	match session_cookie() {
+		Some(cookie) => request.header("Cookie", cookie.as_str()),
+		None => request,
+	}
+}
+
+/// The `data = {...}` object the chapter reader is built from
+pub struct ReaderData {
+	/// Page images
+	pub full_images: Vec<String>,
+	pub thumbnails: Vec<String>,
+	/// Chapter info, with the manga in `content_id` or `url`. Only deep links
+	/// need it, some readers leave it out
+	pub meta: Option<ObjectRef>,
+}
+
+impl ReaderData {
+	pub fn parse(html: &str) -> Result<Self> {
+		let object = match extract_object_literal(html, "data") {
+			Some(object) => js_object_to_json(object),
+			None => {
+				return Err(AidokuError {
+					reason: aidoku::error::AidokuErrorKind::Unimplemented,
+				})
+			}
+		};
+		let data = json::parse(object.as_bytes()).as_object()?;
+		let strings = |key: &str| {
+			data.get(key)
+				.as_array()
+				.map(|array| {
+					array
+						.filter_map(|value| value.as_string().ok())
+						.map(|value| String::from(value.read().trim()))
+						.filter(|value| !value.is_empty())
+						.collect::<Vec<_>>()
+				})
+				.unwrap_or_default()
+		};
+		Ok(Self {
+			full_images: strings("fullimg"),
+			thumbnails: strings("thumbs"),
+			meta: data.get("meta").as_object().ok(),
+		})
 	}
 }
 
@@ -30,14 +111,23 @@ pub struct MangaChanSource {
 	pub base_url: &'static str,
 	pub vol_chap_parser: fn(String, String) -> (f32, f32),
 	pub author_selector: &'static str,
+	/// Selector for the other parts of a multi-part gallery on its
+	/// `/related/` page, empty for sources without galleries
+	pub related_selector: &'static str,
+
+	/// Listing names, as declared in source.json
+	pub listing_random: &'static str,
+	pub listing_popular: &'static str,
+	pub listing_top_rated: &'static str,
+	pub listing_latest: &'static str,
 }
 
 impl MangaChanSource {
-	fn parse_manga_list(&self, html: Node, page_size: usize) -> Result<MangaPageResult> {
-		let node = html.select("div.content_row");
-		let elems = node.array();
-		let has_more = elems.len() == page_size;
-		let manga = elems
+	fn parse_manga_list(&self, html: Node, page: i32) -> Result<MangaPageResult> {
+		let has_more = has_next_page(&html, page);
+		let manga = html
+			.select("div.content_row")
+			.array()
 			.map(|elem| {
 				let manga_node = elem.as_node();
 				let title = manga_node.select("div.manga_row1 h2 a").text().read();
@@ -167,23 +257,41 @@ impl MangaChanSource {
 				need_sort_date=if order_by_date_when_search { "&need_sort_date=true" } else { "" },
 			)
 		};
-		let html = Request::new(&url, HttpMethod::Get).html();
-		self.parse_manga_list(html, if !title.is_empty() { 40 } else { 20 })
+		let html = session_request(&url, HttpMethod::Get).html();
+		self.parse_manga_list(html, page)
 	}
 
-	pub fn get_manga_listing(&self, listing: Listing, _: i32) -> Result<MangaPageResult> {
-		if &listing.name == "Случайная" {
-			let html = Request::new(
-				format!("{}/manga/random", self.base_url).as_str(),
-				HttpMethod::Get,
-			)
-			.html();
-			self.parse_manga_list(html, 10)
-		} else {
-			Err(AidokuError {
-				reason: aidoku::error::AidokuErrorKind::Unimplemented,
-			})
-		}
+	pub fn get_manga_listing(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+		let offset = (page - 1) * 20;
+		let url = match listing.name.as_str() {
+			name if name == self.listing_random => {
+				let html = session_request(
+					format!("{}/manga/random", self.base_url).as_str(),
+					HttpMethod::Get,
+				)
+				.html();
+				// every request gives a new batch, so there is always more
+				let mut result = self.parse_manga_list(html, page)?;
+				result.has_more = !result.manga.is_empty();
+				return Ok(result);
+			}
+			name if name == self.listing_popular => {
+				format!("{}/mostfavorites?offset={offset}", self.base_url)
+			}
+			name if name == self.listing_top_rated => {
+				format!("{}/manga/new?offset={offset}&n=favdesc", self.base_url)
+			}
+			name if name == self.listing_latest => {
+				format!("{}/latest_chapters?offset={offset}", self.base_url)
+			}
+			_ => {
+				return Err(AidokuError {
+					reason: aidoku::error::AidokuErrorKind::Unimplemented,
+				})
+			}
+		};
+		let html = session_request(&url, HttpMethod::Get).html();
+		self.parse_manga_list(html, page)
 	}
 
 	pub fn get_manga_details(&self, id: String) -> Result<Manga> {
@@ -260,7 +368,7 @@ impl MangaChanSource {
 			.collect::<Vec<_>>()
 			.join(", ");
 		let node = html.select("table.table_cha tr[class*=zaliv]");
-		Ok(node
+		let mut chapters = node
 			.array()
 			.map(|elem| {
 				let chapter_node = elem.as_node();
@@ -296,7 +404,108 @@ impl MangaChanSource {
 					lang: String::from("ru"),
 				}
 			})
-			.collect::<Vec<_>>())
+			.collect::<Vec<_>>();
+		if chapters.len() == 1 && !self.related_selector.is_empty() {
+			if let Some(chapter) = chapters.pop() {
+				return Ok(self.get_gallery_chapter_list(id, chapter));
+			}
+		}
+		Ok(chapters)
+	}
+
+	/// Parts of the multi-part gallery `id` belongs to, as (manga id, title)
+	/// in reading order. A gallery without other parts yields only itself.
+	fn gallery_parts(&self, id: &str) -> Vec<(String, String)> {
+		let mut parts = Vec::new();
+		if self.related_selector.is_empty() {
+			parts.push((String::from(id), String::new()));
+			return parts;
+		}
+		cache_manga_page(&format!("{}{id}", self.base_url));
+		let html = Node::new(unsafe { &CACHED_MANGA.clone().unwrap() });
+		parts.push((String::from(id), html.select("a.title_top_a").text().read()));
+
+		let url = format!(
+			"{}{}",
+			self.base_url,
+			id.replacen("/manga/", "/related/", 1)
+		);
+		let related = session_request(&url, HttpMethod::Get).html();
+		for elem in related.select(self.related_selector).array() {
+			let node = elem.as_node();
+			let part_id = node.attr("href").read().replace(self.base_url, "");
+			if !part_id.starts_with("/manga/") || parts.iter().any(|(id, _)| *id == part_id) {
+				continue;
+			}
+			let mut title = node.attr("title").read();
+			if title.is_empty() {
+				title = node.text().read();
+			}
+			parts.push((part_id, title));
+		}
+		// the first part usually has no number
+		parts.sort_by(|a, b| {
+			let a = part_number(&a.1).unwrap_or(1.0);
+			let b = part_number(&b.1).unwrap_or(1.0);
+			a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+		});
+		parts
+	}
+
+	/// Id of the series a gallery belongs to, which is the id of its first
+	/// part, so that every part links to the same entry.
+	pub fn gallery_series_id(&self, id: String) -> String {
+		if self.related_selector.is_empty() {
+			return id;
+		}
+		self.gallery_parts(&id)
+			.into_iter()
+			.next()
+			.map(|(id, _)| id)
+			.unwrap_or(id)
+	}
+
+	/// Chapters for every part of the multi-part gallery `id`, newest first.
+	/// `current` is the chapter of `id` itself, the reader links of the
+	/// other parts are derived from their manga links.
+	pub fn get_gallery_chapter_list(&self, id: String, current: Chapter) -> Vec<Chapter> {
+		let parts = self.gallery_parts(&id);
+		if parts.len() < 2 {
+			let mut chapters = Vec::new();
+			chapters.push(current);
+			return chapters;
+		}
+		// the reader link of `id` may be absolute, the other parts' are paths
+		let current_id = link_path(self.base_url, &current.id);
+		let mut chapters = parts
+			.into_iter()
+			.enumerate()
+			.map(|(index, (part_id, title))| {
+				let (chapter_id, url, date_updated) = if part_id == id {
+					(
+						current_id.clone(),
+						current.url.clone(),
+						current.date_updated,
+					)
+				} else {
+					let chapter_id = part_id.replacen("/manga/", "/online/", 1);
+					let url = format!("{}{}", self.base_url, chapter_id);
+					(chapter_id, url, -1.0)
+				};
+				Chapter {
+					id: chapter_id,
+					title,
+					volume: -1.0,
+					chapter: (index + 1) as f32,
+					date_updated,
+					scanlator: current.scanlator.clone(),
+					url,
+					lang: String::from("ru"),
+				}
+			})
+			.collect::<Vec<_>>();
+		chapters.reverse();
+		chapters
 	}
 
 	pub fn get_page_list(&self, id: String) -> Result<Vec<Page>> {
@@ -306,27 +515,29 @@ impl MangaChanSource {
 		} else {
 			format!("{}{id}", self.base_url)
 		};
-		let html = Request::new(&url, HttpMethod::Get).html().html().read();
-		let (begin, end) = if let Some(begin_) = html.find("fullimg\":[") {
-			// manga-chan and yaoi-chan
-			let begin = begin_ + 10;
-			let end = html[begin..].find(",]").map(|i| i + begin).unwrap_or(0);
-			(begin, end)
-		} else if let Some(begin_) = html.find("fullimg\": [") {
-			// hentai-chan
-			let begin = begin_ + 11;
-			let end = html[begin..].find(']').map(|i| i + begin).unwrap_or(0);
-			(begin, end)
-		} else {
-			(0, 2)
-		};
-		Ok(String::from(&html[begin..end])
-			.replace(|ch| ch == '"' || ch == '\'', "")
-			.split(',')
+		let mut html = session_request(&url, HttpMethod::Get).html().html().read();
+		// the stored session may have expired, log in again once
+		if is_login_stub(&html) && self.login() {
+			html = session_request(&url, HttpMethod::Get).html().html().read();
+		}
+		// still no access, the chapter is for registered users and the source
+		// has no (working) credentials
+		if is_login_stub(&html) {
+			return Ok(Vec::from([Page {
+				index: 0,
+				url: String::new(),
+				base64: String::new(),
+				text: String::from(LOGIN_REQUIRED),
+			}]));
+		}
+		let data = ReaderData::parse(&html)?;
+		Ok(data
+			.full_images
+			.into_iter()
 			.enumerate()
 			.map(|(index, url)| Page {
 				index: index as i32,
-				url: String::from(url.trim()),
+				url,
 				base64: String::new(),
 				text: String::new(),
 			})
@@ -334,7 +545,61 @@ impl MangaChanSource {
 	}
 
 	pub fn modify_image_request(&self, request: Request) {
-		request.header("Referer", self.base_url);
+		let request = request.header("Referer", self.base_url);
+		if let Some(cookie) = session_cookie() {
+			request.header("Cookie", cookie.as_str());
+		}
+	}
+
+	/// Log in with the credentials from the settings and store the session
+	/// cookies, or forget the session when the credentials were cleared.
+	pub fn login(&self) -> bool {
+		for name in SESSION_COOKIES {
+			defaults_set(name, StringRef::from("").0);
+		}
+		let username = defaults_get("username")
+			.as_string()
+			.map(|v| v.read())
+			.unwrap_or_default();
+		let password = defaults_get("password")
+			.as_string()
+			.map(|v| v.read())
+			.unwrap_or_default();
+		if username.is_empty() || password.is_empty() {
+			return false;
+		}
+		let body = format!(
+			"login_name={}&login_password={}&login=submit",
+			urlencode(username),
+			urlencode(password)
+		);
+		let request = Request::new(self.base_url, HttpMethod::Post)
+			.header("Content-Type", "application/x-www-form-urlencoded")
+			.header("Referer", self.base_url)
+			.body(body.as_bytes());
+		request.send();
+		let set_cookie = match request.get_header("Set-Cookie") {
+			Some(header) => header.read(),
+			None => return false,
+		};
+		let mut stored = 0;
+		for name in SESSION_COOKIES {
+			// failed logins answer with `dle_password=deleted`
+			match cookie_value(&set_cookie, name) {
+				Some(value) if !value.is_empty() && value != "deleted" => {
+					defaults_set(name, StringRef::from(value).0);
+					stored += 1;
+				}
+				_ => continue,
+			}
+		}
+		stored == SESSION_COOKIES.len()
+	}
+
+	pub fn handle_notification(&self, notification: String) {
+		if notification == "login" {
+			self.login();
+		}
 	}
 
 	pub fn handle_url(&self, url: String) -> Result<DeepLink> {
@@ -342,24 +607,16 @@ impl MangaChanSource {
 		// ['https:', '', 'manga-chan.me', 'manga', '77319-blessed.html']
 		let split = url.split('/').collect::<Vec<_>>();
 		if split[3] == "manga" {
+			let id = self.gallery_series_id(url.replace(self.base_url, ""));
 			Ok(DeepLink {
-				manga: Some(self.get_manga_details(url.replace(self.base_url, ""))?),
+				manga: Some(self.get_manga_details(id)?),
 				chapter: None,
 			})
 		} else if split[3] == "online" {
-			let html = Request::new(&url, HttpMethod::Get).html().html().read();
-			let begin = if let Some(begin_) = html.find("meta\":{") {
-				begin_ + 7
-			} else if let Some(begin_) = html.find("meta\": {") {
-				begin_ + 8
-			} else {
-				return Err(AidokuError {
-					reason: aidoku::error::AidokuErrorKind::Unimplemented,
-				});
-			};
-			let end = html[begin..].find("},").map(|i| i + begin).unwrap_or(0);
-			let meta = json::parse(format!("{{{}}}", &html[begin..end - 1]).as_bytes());
-			let metaobj = meta.as_object()?;
+			let html = session_request(&url, HttpMethod::Get).html().html().read();
+			let metaobj = ReaderData::parse(&html)?.meta.ok_or(AidokuError {
+				reason: aidoku::error::AidokuErrorKind::Unimplemented,
+			})?;
 			let manga_id = if let Ok(id) = metaobj.get("content_id").as_string() {
 				id.read()
 			} else if let Ok(url) = metaobj.get("url").as_string() {
@@ -369,7 +626,7 @@ impl MangaChanSource {
 					reason: aidoku::error::AidokuErrorKind::Unimplemented,
 				});
 			};
-			let manga = Some(self.get_manga_details(manga_id)?);
+			let manga = Some(self.get_manga_details(self.gallery_series_id(manga_id))?);
 			let chapter = Some(Chapter {
 				id: url.replace(self.base_url, ""),
 				title: String::new(),
@@ -3,7 +3,7 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaPageResult, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 };
 use my_manga_template::template::MyMangaSource;
 
@@ -12,6 +12,8 @@ fn get_instance() -> MyMangaSource {
 		base_url: "https://lkdtt.com",
 		date_format: "dd/MM/yy",
 		should_split_to_get_date: true,
+		novel_genre_id: "21",
+		novel_genre: "Novel",
 		..Default::default()
 	}
 }
@@ -21,6 +23,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	get_instance().get_manga_list(filters, page)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	get_instance().get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	get_instance().get_manga_details(id)
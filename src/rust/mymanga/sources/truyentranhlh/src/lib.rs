@@ -3,7 +3,7 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, Manga, MangaPageResult, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 };
 use my_manga_template::template::MyMangaSource;
 
@@ -11,6 +11,8 @@ fn get_instance() -> MyMangaSource {
 	MyMangaSource {
 		base_url: "https://truyentranhlh.net",
 		manga_details_path: "/truyen-tranh",
+		novel_genre_id: "57",
+		novel_genre: "Light Novel",
 		..Default::default()
 	}
 }
@@ -20,6 +22,11 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 	get_instance().get_manga_list(filters, page)
 }
 
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	get_instance().get_manga_listing(listing, page)
+}
+
 #[get_manga_details]
 fn get_manga_details(id: String) -> Result<Manga> {
 	get_instance().get_manga_details(id)
@@ -1,5 +1,5 @@
 use aidoku::{
-	std::{html::Node, String, Vec},
+	std::{String, Vec},
 	MangaContentRating, MangaViewer,
 };
 
@@ -44,23 +44,6 @@ pub fn urlencode(string: String) -> String {
 	String::from_utf8(result).unwrap_or_default()
 }
 
-pub fn text_with_newlines(node: Node) -> String {
-	let html = node.html().read();
-	if !String::from(html.trim()).is_empty() {
-		Node::new_fragment(
-			node.html()
-				.read()
-				.replace("<br>", "{{ .LINEBREAK }}")
-				.as_bytes(),
-		)
-		.text()
-		.read()
-		.replace("{{ .LINEBREAK }}", "\n")
-	} else {
-		String::new()
-	}
-}
-
 pub fn category_parser(categories: &Vec<String>) -> (MangaContentRating, MangaViewer) {
 	let mut nsfw = MangaContentRating::Safe;
 	let mut viewer = MangaViewer::Rtl;
@@ -80,3 +63,45 @@ pub fn category_parser(categories: &Vec<String>) -> (MangaContentRating, MangaVi
 	}
 	(nsfw, viewer)
 }
+
+// a chapter is read as text when it has no images, or when the manga is under
+// the novel genre, comic chapters may have captions besides their images
+pub fn is_text_chapter(images: usize, categories: &[String], novel_genre: &str) -> bool {
+	images == 0 || (!novel_genre.is_empty() && categories.iter().any(|genre| genre == novel_genre))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn categories(names: &[&str]) -> Vec<String> {
+		names.iter().map(|name| String::from(*name)).collect()
+	}
+
+	#[test]
+	fn reads_chapters_without_images_as_text() {
+		assert!(is_text_chapter(0, &categories(&[]), ""));
+		assert!(is_text_chapter(0, &categories(&["Action"]), "Light Novel"));
+	}
+
+	#[test]
+	fn keeps_the_images_of_comic_chapters_with_text() {
+		// a credits paragraph above the pages doesn't make a novel
+		assert!(!is_text_chapter(
+			12,
+			&categories(&["Action"]),
+			"Light Novel"
+		));
+		assert!(!is_text_chapter(12, &categories(&["Light Novel"]), ""));
+	}
+
+	#[test]
+	fn reads_novel_chapters_with_illustrations_as_text() {
+		assert!(is_text_chapter(
+			2,
+			&categories(&["Light Novel", "Fantasy"]),
+			"Light Novel"
+		));
+		assert!(!is_text_chapter(2, &categories(&["Novel"]), "Light Novel"));
+	}
+}
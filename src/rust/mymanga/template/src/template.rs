@@ -1,15 +1,17 @@
 use crate::{helper::*, html_entity_decoder::decode_html_entities};
 use aidoku::{
-	error::Result,
+	error::{AidokuError, AidokuErrorKind, Result},
 	prelude::*,
 	std::{
+		defaults::defaults_get,
 		html::Node,
 		net::{HttpMethod, Request},
 		ArrayRef, String, StringRef, Vec,
 	},
-	Chapter, DeepLink, Filter, FilterType, Manga, MangaContentRating, MangaPageResult, MangaStatus,
-	MangaViewer, Page,
+	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaContentRating, MangaPageResult,
+	MangaStatus, MangaViewer, Page,
 };
+use common::text::{paragraphs, text_pages, text_with_newlines};
 
 pub struct MyMangaSource {
 	pub base_url: &'static str,
@@ -20,6 +22,32 @@ pub struct MyMangaSource {
 	pub timezone: &'static str,
 	pub should_split_to_get_date: bool,
 	pub split_str: &'static str,
+
+	// listing names, as declared in source.json
+	pub listing_newest: &'static str,
+	pub listing_top_views: &'static str,
+	pub listing_updated: &'static str,
+	// path of the recently updated list
+	pub updated_path: &'static str,
+
+	// filter names, as declared in filters.json
+	pub filter_sort: &'static str,
+	pub filter_status: &'static str,
+
+	// labels of the info items on the details page
+	pub details_author: &'static str,
+	pub details_status: &'static str,
+
+	pub status_ongoing: &'static str,
+	pub status_hiatus: &'static str,
+	pub status_completed: &'static str,
+	// placeholder shown when the translation team is unknown
+	pub unknown_scanlator: &'static str,
+
+	// genre id of light novels, needed for the hideNovels setting
+	pub novel_genre_id: &'static str,
+	// name of that genre on the details page, marks chapters read as text
+	pub novel_genre: &'static str,
 }
 
 impl Default for MyMangaSource {
@@ -32,6 +60,25 @@ impl Default for MyMangaSource {
 			timezone: "Asia/Ho_Chi_Minh",
 			should_split_to_get_date: false,
 			split_str: " - ",
+
+			listing_newest: "Truyện mới",
+			listing_top_views: "Xem nhiều",
+			listing_updated: "Mới cập nhật",
+			updated_path: "/danh-sach",
+
+			filter_sort: "Sắp xếp",
+			filter_status: "Tình trạng",
+
+			details_author: "Tác giả",
+			details_status: "Tình trạng",
+
+			status_ongoing: "Đang tiến hành",
+			status_hiatus: "Tạm ngưng",
+			status_completed: "Đã hoàn thành",
+			unknown_scanlator: "Đang cập nhật",
+
+			novel_genre_id: "",
+			novel_genre: "",
 		}
 	}
 }
@@ -86,6 +133,18 @@ impl MyMangaSource {
 		(manga, has_more)
 	}
 
+	fn fetch_manga_list(&self, url: &str) -> MangaPageResult {
+		let html = Request::new(url, HttpMethod::Get).html();
+		let node = html.select("div.thumb-item-flow.col-6.col-md-2");
+		let elems = node.array();
+		let (manga, has_more) = self.parse_manga_list(elems);
+		MangaPageResult { manga, has_more }
+	}
+
+	fn hide_novels(&self) -> bool {
+		!self.novel_genre_id.is_empty() && defaults_get("hideNovels").as_bool().unwrap_or(false)
+	}
+
 	pub fn get_manga_list(&self, filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 		let mut included_tags: Vec<String> = Vec::new();
 		let mut excluded_tags: Vec<String> = Vec::new();
@@ -112,7 +171,7 @@ impl MyMangaSource {
 					}
 				}
 				_ => match filter.name.as_str() {
-					"Sắp xếp" => {
+					name if name == self.filter_sort => {
 						url.push_str("&sort=");
 						match filter.value.as_int().unwrap_or(-1) {
 							0 => url.push_str("az"),
@@ -124,7 +183,7 @@ impl MyMangaSource {
 							_ => continue,
 						}
 					}
-					"Tình trạng" => {
+					name if name == self.filter_status => {
 						let value = filter.value.as_int().unwrap_or(-1);
 						if value <= 0 {
 							continue;
@@ -135,17 +194,43 @@ impl MyMangaSource {
 				},
 			}
 		}
+		if self.hide_novels()
+			&& !included_tags.iter().any(|tag| tag == self.novel_genre_id)
+			&& !excluded_tags.iter().any(|tag| tag == self.novel_genre_id)
+		{
+			excluded_tags.push(String::from(self.novel_genre_id));
+		}
 		if !excluded_tags.is_empty() {
 			url.push_str(format!("&reject_genres={}", excluded_tags.join(",")).as_str());
 		}
 		if !included_tags.is_empty() {
 			url.push_str(format!("&accept_genres={}", included_tags.join(",")).as_str());
 		}
-		let html = Request::new(&url, HttpMethod::Get).html();
-		let node = html.select("div.thumb-item-flow.col-6.col-md-2");
-		let elems = node.array();
-		let (manga, has_more) = self.parse_manga_list(elems);
-		Ok(MangaPageResult { manga, has_more })
+		Ok(self.fetch_manga_list(&url))
+	}
+
+	pub fn get_manga_listing(&self, listing: Listing, page: i32) -> Result<MangaPageResult> {
+		let hide_novels = self.hide_novels();
+		let sort = match listing.name.as_str() {
+			name if name == self.listing_newest => "new",
+			name if name == self.listing_top_views => "top",
+			// the updated list can't exclude genres, search gives the same order
+			name if name == self.listing_updated && !hide_novels => {
+				let url = format!("{}{}?page={page}", self.base_url, self.updated_path);
+				return Ok(self.fetch_manga_list(&url));
+			}
+			name if name == self.listing_updated => "update",
+			_ => {
+				return Err(AidokuError {
+					reason: AidokuErrorKind::Unimplemented,
+				})
+			}
+		};
+		let mut url = format!("{}/tim-kiem?sort={sort}&page={page}", self.base_url);
+		if hide_novels {
+			url.push_str(format!("&reject_genres={}", self.novel_genre_id).as_str());
+		}
+		Ok(self.fetch_manga_list(&url))
 	}
 
 	pub fn get_manga_details(&self, id: String) -> Result<Manga> {
@@ -154,20 +239,26 @@ impl MyMangaSource {
 		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
 		let title = String::from(html.select("span.series-name").text().read().trim());
 		let author = String::from(
-			html.select("div.info-item:contains(Tác giả) span.info-value")
-				.text()
-				.read()
-				.trim(),
+			html.select(&format!(
+				"div.info-item:contains({}) span.info-value",
+				self.details_author
+			))
+			.text()
+			.read()
+			.trim(),
 		);
 		let status = match html
-			.select("div.info-item:contains(Tình trạng) span.info-value")
+			.select(&format!(
+				"div.info-item:contains({}) span.info-value",
+				self.details_status
+			))
 			.text()
 			.read()
 			.trim()
 		{
-			"Đang tiến hành" => MangaStatus::Ongoing,
-			"Tạm ngưng" => MangaStatus::Hiatus,
-			"Đã hoàn thành" => MangaStatus::Completed,
+			status if status == self.status_ongoing => MangaStatus::Ongoing,
+			status if status == self.status_hiatus => MangaStatus::Hiatus,
+			status if status == self.status_completed => MangaStatus::Completed,
 			_ => MangaStatus::Unknown,
 		};
 		let cover_style = html.select("div.content.img-in-ratio").attr("style").read();
@@ -208,7 +299,7 @@ impl MyMangaSource {
 		let scanlator = {
 			let original = String::from(html.select("div.fantrans-value a").text().read().trim());
 			let temp = decode_html_entities(&original);
-			if temp == "Đang cập nhật" {
+			if temp == self.unknown_scanlator {
 				String::new()
 			} else {
 				String::from(temp)
@@ -271,7 +362,18 @@ impl MyMangaSource {
 	pub fn get_page_list(&self, id: String) -> Result<Vec<Page>> {
 		let url = format!("{}{id}", self.base_url);
 		let html = Request::new(&url, HttpMethod::Get).html();
-		let node = html.select("div#chapter-content img");
+		let content = html.select("div#chapter-content");
+		let images = content.select("img").array().len();
+		// images in a novel chapter are illustrations between the text
+		let categories = if images > 0 && !self.novel_genre.is_empty() {
+			self.chapter_categories(&id)
+		} else {
+			Vec::new()
+		};
+		if is_text_chapter(images, &categories, self.novel_genre) {
+			return Ok(self.get_text_pages(content));
+		}
+		let node = content.select("img");
 		let elems = node.array();
 		let mut pages = Vec::with_capacity(elems.len());
 		for (idx, elem) in elems.enumerate() {
@@ -287,6 +389,30 @@ impl MyMangaSource {
 		Ok(pages)
 	}
 
+	// genres of the manga a chapter belongs to, from its details page
+	fn chapter_categories(&self, chapter_id: &str) -> Vec<String> {
+		let manga_id = match chapter_id.trim_end_matches('/').rsplit_once('/') {
+			Some((manga_id, _)) => manga_id,
+			None => return Vec::new(),
+		};
+		cache_manga_page(&format!("{}{manga_id}", self.base_url));
+		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
+		html.select("a[href*=the-loai] span.badge")
+			.array()
+			.map(|elem| String::from(elem.as_node().text().read().trim()))
+			.collect()
+	}
+
+	// the body of a light novel chapter, split into text pages
+	fn get_text_pages(&self, content: Node) -> Vec<Page> {
+		let paragraphs = paragraphs(content)
+			.iter()
+			.map(|paragraph| String::from(decode_html_entities(paragraph).trim()))
+			.filter(|paragraph| !paragraph.is_empty())
+			.collect::<Vec<_>>();
+		text_pages(paragraphs)
+	}
+
 	pub fn modify_image_request(&self, request: Request) {
 		let mut referer_url = String::from(self.base_url);
 		referer_url.push('/');
@@ -308,7 +434,7 @@ impl MyMangaSource {
 					date_updated: -1.0,
 					scanlator: String::new(),
 					url,
-					lang: String::from("vi"),
+					lang: String::from(self.language),
 				}),
 			})
 		} else if id.contains(self.manga_details_path) {
@@ -0,0 +1,69 @@
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind, Result},
+	std::String,
+};
+
+/// The sections of an Otaku Sanctuary site that ids and URLs can point to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContentKind {
+	/// `/manga-detail/{id}/{slug}`
+	Manga,
+	/// `/novel-detail/{id}/{slug}`
+	Novel,
+	/// `/chapter/{id}/{slug}`
+	MangaChapter,
+	/// `/novel-chapter/{id}/{slug}`, read as text pages
+	NovelChapter,
+	/// `/wallpaper/{id}`, a single image shown as a one-page chapter
+	Wallpaper,
+	/// `/Cosplay/{id}`, same layout as wallpapers
+	Cosplay,
+}
+
+impl ContentKind {
+	/// Kind of an id or full URL, from the first segment of its path.
+	pub fn parse(id: &str) -> Result<ContentKind> {
+		let path = path_of(id);
+		let section = path
+			.trim_start_matches('/')
+			.split('/')
+			.next()
+			.unwrap_or_default()
+			.to_ascii_lowercase();
+		match section.as_str() {
+			"manga-detail" => Ok(ContentKind::Manga),
+			"novel-detail" => Ok(ContentKind::Novel),
+			"chapter" => Ok(ContentKind::MangaChapter),
+			"novel-chapter" => Ok(ContentKind::NovelChapter),
+			"wallpaper" => Ok(ContentKind::Wallpaper),
+			"cosplay" => Ok(ContentKind::Cosplay),
+			_ => Err(AidokuError {
+				reason: AidokuErrorKind::Unimplemented,
+			}),
+		}
+	}
+
+	/// Wallpapers and cosplay sets are images without a chapter list of their
+	/// own, their only chapter id is the item id followed by `/image`.
+	pub fn is_image_set(self) -> bool {
+		matches!(self, ContentKind::Wallpaper | ContentKind::Cosplay)
+	}
+
+	pub fn is_chapter(self) -> bool {
+		matches!(self, ContentKind::MangaChapter | ContentKind::NovelChapter)
+	}
+}
+
+/// Path of a URL, or the id itself if it is already a path.
+pub fn path_of(url: &str) -> String {
+	match url.find("://") {
+		Some(scheme_end) => {
+			let rest = &url[scheme_end + 3..];
+			match rest.find('/') {
+				Some(path_start) => String::from(&rest[path_start..]),
+				None => String::from("/"),
+			}
+		}
+		None => String::from(url),
+	}
+}
@@ -1,9 +1,11 @@
 use aidoku::{
-	std::{current_date, defaults::defaults_get, html::Node, String, StringRef, Vec},
+	std::{current_date, defaults::defaults_get, String, StringRef, Vec},
 	MangaContentRating, MangaViewer,
 };
 use alloc::string::ToString;
 
+use crate::resolver::expand_tokens;
+
 pub fn extract_f32_from_string(title: String, text: String) -> f32 {
 	text.replace(&title, "")
 		.chars()
@@ -38,30 +40,63 @@ pub fn urlencode(string: String) -> String {
 	String::from_utf8(result).unwrap_or_default()
 }
 
-pub fn get_lang_code() -> String {
-	let mut code = String::from("vn");
+// codes the site uses in its flags and `Lang` parameter, with the matching
+// language codes, the site codes are the values of the languages in source.json
+pub const LANGUAGES: [(&str, &str); 5] = [
+	("vn", "vi"),
+	("us", "en"),
+	("fr", "fr"),
+	("es", "es"),
+	("it", "it"),
+];
+
+pub fn lang_from_code(code: &str) -> String {
+	String::from(
+		LANGUAGES
+			.iter()
+			.find(|(site_code, _)| *site_code == code)
+			.map(|(_, lang)| *lang)
+			.unwrap_or(code),
+	)
+}
+
+// site code of a flag image, e.g. `https://ipdata.co/flags/us.png`
+pub fn flag_code(src: &str) -> String {
+	String::from(
+		src.rsplit('/')
+			.next()
+			.unwrap_or_default()
+			.trim_end_matches(".png"),
+	)
+}
+
+// site codes of the selected languages, empty when "All" is selected
+pub fn get_lang_codes() -> Vec<String> {
+	let mut codes: Vec<String> = Vec::new();
 	if let Ok(languages) = defaults_get("languages").as_array() {
-		if let Ok(language) = languages.get(0).as_string() {
-			code = language.read();
+		for language in languages {
+			if let Ok(language) = language.as_string() {
+				let code = language.read();
+				if code == "All" {
+					return Vec::new();
+				}
+				codes.push(code);
+			}
 		}
 	}
-	code
+	if codes.is_empty() {
+		codes.push(String::from(LANGUAGES[0].0));
+	}
+	codes
 }
 
-pub fn text_with_newlines(node: Node) -> String {
-	let html = node.html().read();
-	if !String::from(html.trim()).is_empty() {
-		Node::new_fragment(
-			node.html()
-				.read()
-				.replace("<br>", "{{ .LINEBREAK }}")
-				.as_bytes(),
-		)
-		.text()
-		.read()
-		.replace("{{ .LINEBREAK }}", "\n")
+// value of the site's `Lang` parameter, which takes one language or "All"
+pub fn get_lang_code() -> String {
+	let codes = get_lang_codes();
+	if codes.len() == 1 {
+		codes[0].clone()
 	} else {
-		String::new()
+		String::from("All")
 	}
 }
 
@@ -122,45 +157,17 @@ pub fn convert_time(ago: String) -> f64 {
 }
 
 pub fn url_replacer(url: String, vi: String) -> String {
+	let url = expand_tokens(&url, &vi);
+	proxy_url(url, vi)
+}
+
+// route images from hosts the app can't load directly through a proxy,
+// `url` has its tokens expanded already
+pub fn proxy_url(url: String, vi: String) -> String {
 	let mut url = url;
-	url = url
-		.replace("_h_", "http")
-		.replace("_e_", "/extendContent/Manga")
-		.replace("_r_", "/extendContent/MangaRaw");
-	if &url[0..2] == "//" {
-		url = String::from("https:") + &url;
-	}
 	if url.contains("drive.google.com") {
 		return url;
 	}
-	url = match &url[0..5] {
-		"[GDP]" => url.replace("[GDP]", "https://drive.google.com/uc?export=view&id="),
-		"[GDT]" => {
-			if &get_lang_code() == "us" {
-				url.replace("image2.otakuscan.net", "image3.shopotaku.net")
-					.replace("image2.otakusan.net", "image3.shopotaku.net")
-			} else {
-				url
-			}
-		}
-		"[IS1]" => {
-			let mut url_temp = url.replace("[IS1]", "https://imagepi.otakuscan.net/");
-			if url_temp.contains("vi=") && !url_temp.contains("otakusan.net_") {
-				url_temp
-			} else {
-				if url.contains('?') {
-					url_temp.push_str("&vi=");
-				} else {
-					url_temp.push_str("?vi=");
-				}
-				url_temp.push_str(&vi);
-				url_temp
-			}
-		}
-		"[IS3]" => url.replace("[IS3]", "https://image3.otakusan.net/"),
-		"[IO3]" => url.replace("[IO3]", "http://image3.shopotaku.net/"),
-		_ => url,
-	};
 	if url.contains("/Content/Workshop") || url.contains("otakusan") || url.contains("myrockmanga")
 	{
 		return url;
@@ -1,4 +1,6 @@
 #![no_std]
 extern crate alloc;
+pub mod content;
 pub mod helper;
+pub mod resolver;
 pub mod template;
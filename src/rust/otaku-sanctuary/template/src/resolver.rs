@@ -0,0 +1,226 @@
+// Turns the image links of chapter payloads into URLs the app can load:
+// expands the site's host tokens, follows Google Drive's confirm page and
+// swaps in a mirror when an image host doesn't answer.
+use aidoku::{
+	prelude::format,
+	std::{
+		net::{HttpMethod, Request},
+		String, Vec,
+	},
+};
+use common::image::image_available;
+
+use crate::helper::{proxy_url, urlencode};
+
+const DRIVE_VIEW: &str = "https://drive.google.com/uc?export=view&id=";
+
+// prefixes of payload links and the hosts they stand for, `[GDT]` only marks
+// links that already hold their host
+const HOST_TOKENS: [(&str, &str); 5] = [
+	("[GDP]", DRIVE_VIEW),
+	("[GDT]", ""),
+	("[IS1]", "https://imagepi.otakuscan.net/"),
+	("[IS3]", "https://image3.otakusan.net/"),
+	("[IO3]", "http://image3.shopotaku.net/"),
+];
+
+// hosts serving the same files, in the order they are tried
+const MIRRORS: [&[&str]; 2] = [
+	&[
+		"image2.otakuscan.net",
+		"image2.otakusan.net",
+		"image3.shopotaku.net",
+	],
+	&["image3.otakusan.net", "image3.shopotaku.net"],
+];
+
+// the English site serves its images from this host first
+const ENGLISH_HOST: &str = "image3.shopotaku.net";
+
+pub struct ImageResolver {
+	vi: String,
+	lang: String,
+	referer: String,
+	// hosts checked in this chapter and the host used in their place
+	hosts: Vec<(String, String)>,
+}
+
+impl ImageResolver {
+	pub fn new(vi: String, lang: String, referer: &str) -> Self {
+		ImageResolver {
+			vi,
+			lang,
+			referer: String::from(referer),
+			hosts: Vec::new(),
+		}
+	}
+
+	pub fn resolve(&mut self, raw: &str) -> String {
+		let url = expand_tokens(raw, &self.vi);
+		if url.contains("drive.google.com") {
+			return resolve_drive(&url);
+		}
+		let host = String::from(host_of(&url));
+		let working_host = match self.hosts.iter().find(|(checked, _)| *checked == host) {
+			Some((_, working_host)) => working_host.clone(),
+			None => {
+				let working_host = self.find_working_host(&url);
+				self.hosts.push((host.clone(), working_host.clone()));
+				working_host
+			}
+		};
+		proxy_url(url.replacen(&host, &working_host, 1), self.vi.clone())
+	}
+
+	// first mirror answering for this image, the image's own host if none does
+	fn find_working_host(&self, url: &str) -> String {
+		let candidates = mirrors(url, &self.lang);
+		if candidates.len() > 1 {
+			for candidate in &candidates {
+				if image_available(
+					&proxy_url(candidate.clone(), self.vi.clone()),
+					&[("Referer", &self.referer)],
+				) {
+					return String::from(host_of(candidate));
+				}
+			}
+		}
+		String::from(host_of(url))
+	}
+}
+
+// expand the shorthands and host tokens the site stores in chapter payloads
+pub fn expand_tokens(url: &str, vi: &str) -> String {
+	let mut url = url
+		.trim()
+		.replace("_h_", "http")
+		.replace("_e_", "/extendContent/Manga")
+		.replace("_r_", "/extendContent/MangaRaw");
+	if url.starts_with("//") {
+		url = format!("https:{url}");
+	}
+	for (token, host) in HOST_TOKENS {
+		if let Some(rest) = url.strip_prefix(token) {
+			url = format!("{host}{rest}");
+			if token == "[IS1]" && !(url.contains("vi=") && !url.contains("otakusan.net_")) {
+				url.push(if url.contains('?') { '&' } else { '?' });
+				url.push_str("vi=");
+				url.push_str(vi);
+			}
+			break;
+		}
+	}
+	url
+}
+
+// the URL followed by the same file on its mirrors
+pub fn mirrors(url: &str, lang: &str) -> Vec<String> {
+	let host = host_of(url);
+	let mut urls: Vec<String> = Vec::new();
+	urls.push(String::from(url));
+	if let Some(group) = MIRRORS.iter().find(|group| group.contains(&host)) {
+		for mirror in group.iter().filter(|mirror| **mirror != host) {
+			let mirror_url = url.replacen(host, mirror, 1);
+			if lang == "us" && *mirror == ENGLISH_HOST {
+				urls.insert(0, mirror_url);
+			} else {
+				urls.push(mirror_url);
+			}
+		}
+	}
+	urls
+}
+
+pub fn host_of(url: &str) -> &str {
+	let rest = match url.find("://") {
+		Some(scheme_end) => &url[scheme_end + 3..],
+		None => url,
+	};
+	rest.split(|c| c == '/' || c == '?' || c == '#')
+		.next()
+		.unwrap_or_default()
+}
+
+// file id of a Drive link, from `?id=` or a `/file/d/{id}/` path
+pub fn drive_file_id(url: &str) -> Option<String> {
+	let id = if let Some(at) = url.find("/file/d/") {
+		url[at + 8..].split(|c| c == '/' || c == '?').next()
+	} else {
+		url.split(|c| c == '?' || c == '&')
+			.find_map(|param| param.strip_prefix("id="))
+			.map(|id| id.split('#').next().unwrap_or_default())
+	};
+	id.filter(|id| !id.is_empty()).map(String::from)
+}
+
+// Drive answers files too large to scan with an HTML warning page instead
+// of the file, whose download form or link holds the direct URL
+fn resolve_drive(url: &str) -> String {
+	let id = match drive_file_id(url) {
+		Some(id) => id,
+		None => return String::from(url),
+	};
+	let view_url = format!("{}{}", DRIVE_VIEW, id);
+	// only the headers are fetched, the page is downloaded when it's a warning
+	let request = Request::new(&view_url, HttpMethod::Head);
+	request.send();
+	let is_page = request
+		.get_header("Content-Type")
+		.map(|content_type| content_type.read().starts_with("text/html"))
+		.unwrap_or(false);
+	if !is_page {
+		return view_url;
+	}
+	let page = Request::new(&view_url, HttpMethod::Get).string();
+	parse_drive_confirm(&page).unwrap_or(view_url)
+}
+
+// direct URL from a Drive confirm page, either the current
+// `form#download-form` or the older `a#uc-download-link`
+pub fn parse_drive_confirm(page: &str) -> Option<String> {
+	if let Some(form_at) = page.find("id=\"download-form\"") {
+		let form_start = page[..form_at].rfind('<')?;
+		let form = &page[form_start..];
+		let form = &form[..form.find("</form>").unwrap_or(form.len())];
+		let action = decode_attr(tag_attr(form, "action")?);
+		let mut params: Vec<String> = Vec::new();
+		for input in form.split("<input").skip(1) {
+			let input = &input[..input.find('>').unwrap_or(input.len())];
+			if tag_attr(input, "type") != Some("hidden") {
+				continue;
+			}
+			if let (Some(name), Some(value)) = (tag_attr(input, "name"), tag_attr(input, "value")) {
+				params.push(format!("{}={}", name, urlencode(decode_attr(value))));
+			}
+		}
+		if params.is_empty() {
+			return None;
+		}
+		return Some(format!("{}?{}", action, params.join("&")));
+	}
+	let link_at = page.find("id=\"uc-download-link\"")?;
+	let link_start = page[..link_at].rfind('<')?;
+	let link = &page[link_start..];
+	let link = &link[..link.find('>').unwrap_or(link.len())];
+	let href = decode_attr(tag_attr(link, "href")?);
+	if href.starts_with('/') {
+		Some(format!("https://drive.google.com{href}"))
+	} else {
+		Some(href)
+	}
+}
+
+// value of a double quoted attribute in the text of a tag
+fn tag_attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+	let pattern = format!(" {name}=\"");
+	let start = tag.find(&pattern)? + pattern.len();
+	let len = tag[start..].find('"')?;
+	Some(&tag[start..start + len])
+}
+
+fn decode_attr(value: &str) -> String {
+	value
+		.replace("&amp;", "&")
+		.replace("&#39;", "'")
+		.replace("&quot;", "\"")
+}
@@ -1,5 +1,5 @@
 use aidoku::{
-	error::Result,
+	error::{AidokuError, AidokuErrorKind, Result},
 	prelude::*,
 	std::{
 		html::Node,
@@ -11,7 +11,17 @@ use aidoku::{
 	MangaStatus, MangaViewer, Page,
 };
 
-use crate::helper::*;
+use common::text::{paragraphs, text_pages, text_with_newlines};
+
+use crate::{
+	content::{path_of, ContentKind},
+	helper::*,
+	resolver::ImageResolver,
+};
+
+// links to the other translations of a title on its details page
+const TRANSLATIONS_SELECTOR: &str =
+	"div.other-lang a[href*=manga-detail], div.other-lang a[href*=novel-detail]";
 
 static mut CACHED_MANGA_ID: Option<String> = None;
 static mut CACHED_MANGA: Option<Vec<u8>> = None;
@@ -151,9 +161,14 @@ impl OtakuSanctuarySource {
 		let (manga, has_more) = if search_request {
 			let collections_node = resp.select("div.collection");
 			let collections = collections_node.array();
-			let manga_section = collections.get(collections.len() - 3);
-			let node = manga_section.as_node();
-			let (mut manga_list, _) = self.parse_manga_list(node.select("div.mdl-card").array());
+			// manga results are the third collection from the end, if any
+			let (mut manga_list, _) = match collections.len().checked_sub(3) {
+				Some(index) => {
+					let node = collections.get(index).as_node();
+					self.parse_manga_list(node.select("div.mdl-card").array())
+				}
+				None => (Vec::new(), false),
+			};
 
 			let wallpaper_elems = resp.select("div.picture-mason");
 			let (mut wallpaper_list, _) = self.parse_image_list(wallpaper_elems.array());
@@ -175,7 +190,11 @@ impl OtakuSanctuarySource {
 			"Ecchi Land" => format!("{}/Manga/EcchiNewest", self.base_url),
 			"Wallpaper" => format!("{}/WallPaper/Newest?type=Newest&offset=", self.base_url),
 			"Cosplay" => format!("{}/Cosplay/Newest?type=Newest&offset=", self.base_url),
-			_ => unreachable!(),
+			_ => {
+				return Err(AidokuError {
+					reason: AidokuErrorKind::Unimplemented,
+				})
+			}
 		};
 		match listing.name.as_str() {
 			"Completed" | "New Titles" | "For Boys" | "For Girls" | "Ecchi Land" => {
@@ -204,15 +223,18 @@ impl OtakuSanctuarySource {
 				let (manga, has_more) = self.parse_image_list(elems);
 				Ok(MangaPageResult { manga, has_more })
 			}
-			_ => unreachable!(),
+			_ => Err(AidokuError {
+				reason: AidokuErrorKind::Unimplemented,
+			}),
 		}
 	}
 
 	pub fn get_manga_details(&self, id: String) -> Result<Manga> {
+		let kind = ContentKind::parse(&id)?;
 		let url = format!("{}{id}", self.base_url);
 		cache_manga_page(&url);
 		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
-		if id.contains("manga-detail") {
+		if kind == ContentKind::Manga || kind == ContentKind::Novel {
 			let title = capitalize_first_letter(String::from(
 				html.select("h1.title.text-lg-left.text-overflow-2-line")
 					.text()
@@ -258,6 +280,7 @@ impl OtakuSanctuarySource {
 				.read()
 				.trim()
 			{
+				_ if kind == ContentKind::Novel => MangaViewer::Scroll,
 				"Manhua" | "Manhwa" => MangaViewer::Scroll,
 				"VnComic" => MangaViewer::Ltr,
 				_ => MangaViewer::Rtl,
@@ -279,8 +302,8 @@ impl OtakuSanctuarySource {
 				nsfw,
 				viewer,
 			})
-		} else if id.contains("wallpaper") || id.contains("Cosplay") {
-			let author = if id.contains("wallpaper") {
+		} else if kind.is_image_set() {
+			let author = if kind == ContentKind::Wallpaper {
 				capitalize_first_letter(String::from(
 					html.select("tr:contains(Artist) td span.capitalize")
 						.text()
@@ -290,7 +313,7 @@ impl OtakuSanctuarySource {
 			} else {
 				String::new()
 			};
-			let description = if id.contains("wallpaper") {
+			let description = if kind == ContentKind::Wallpaper {
 				let mut ret: Vec<String> = Vec::with_capacity(2);
 				let original_source = String::from(
 					html.select("tr:contains(Nguồn) td span:not(.nav)")
@@ -324,7 +347,8 @@ impl OtakuSanctuarySource {
 					.read()
 					.trim()
 					.split(" > ")
-					.collect::<Vec<_>>()[1],
+					.nth(1)
+					.unwrap_or_default(),
 			);
 			Ok(Manga {
 				id,
@@ -340,77 +364,114 @@ impl OtakuSanctuarySource {
 				viewer: MangaViewer::Ltr,
 			})
 		} else {
-			unreachable!()
+			Err(AidokuError {
+				reason: AidokuErrorKind::Unimplemented,
+			})
 		}
 	}
 
+	// chapters of a title page, tagged with the page's language and group,
+	// also returns the site's code for that language
+	fn parse_chapter_table(&self, html: &Node) -> (String, Vec<Chapter>) {
+		let scanlator = html
+			.select("tr:contains(Nhóm Dịch) a")
+			.attr("title")
+			.read()
+			.replace(" collections", "");
+		let site_lang = flag_code(&html.select("h1.title img.flag").attr("src").read());
+		let lang = lang_from_code(&site_lang);
+
+		let node = html.select("tr.chapter");
+		let elems = node.array();
+		let mut chapters: Vec<Chapter> = Vec::with_capacity(elems.len());
+		for elem in elems {
+			let elem_node = elem.as_node();
+			let cells_node = elem_node.select("td");
+			let cells = cells_node.array();
+			let mut chapter: Chapter = Chapter {
+				id: String::new(),
+				title: String::new(),
+				volume: -1.0,
+				chapter: -1.0,
+				date_updated: -1.0,
+				scanlator: if scanlator.as_str() == "Unknown" {
+					String::new()
+				} else {
+					scanlator.clone()
+				},
+				url: String::new(),
+				lang: lang.clone(),
+			};
+			for (idx, cell) in cells.enumerate() {
+				let node = cell.as_node();
+				match idx {
+					0 => {
+						chapter.chapter =
+							extract_f32_from_string(String::new(), node.text().read());
+					}
+					1 => {
+						let anchor = node.select("a");
+						chapter.id = anchor.attr("href").read();
+						chapter.title = String::from(anchor.text().read().trim());
+						chapter.url = format!("{}{}", self.base_url, chapter.id);
+					}
+					3 => {
+						chapter.date_updated =
+							convert_time(String::from(node.text().read().trim()));
+					}
+					_ => continue,
+				}
+			}
+			chapters.push(chapter);
+		}
+		(site_lang, chapters)
+	}
+
 	pub fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>> {
+		let kind = ContentKind::parse(&id)?;
 		let url = format!("{}{id}", self.base_url);
 		cache_manga_page(&url);
 		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
-		if id.contains("manga-detail") {
-			let scanlator = html
-				.select("tr:contains(Nhóm Dịch) a")
-				.attr("title")
-				.read()
-				.replace(" collections", "");
-			let mut lang = html
-				.select("h1.title img.flag")
-				.attr("src")
-				.read()
-				.replace("https://ipdata.co/flags/", "")
-				.replace(".png", "");
-			lang = String::from(match lang.as_str() {
-				"us" => "en",
-				"vn" => "vi",
-				_ => lang.as_str(),
-			});
+		if kind == ContentKind::Manga || kind == ContentKind::Novel {
+			let wanted = get_lang_codes();
+			let is_wanted =
+				|code: &str| wanted.is_empty() || wanted.iter().any(|lang| lang == code);
+			let (site_lang, main_chapters) = self.parse_chapter_table(&html);
 
-			let node = html.select("tr.chapter");
-			let elems = node.array();
-			let mut chapters: Vec<Chapter> = Vec::with_capacity(elems.len());
-			for elem in elems {
-				let elem_node = elem.as_node();
-				let cells_node = elem_node.select("td");
-				let cells = cells_node.array();
-				let mut chapter: Chapter = Chapter {
-					id: String::new(),
-					title: String::new(),
-					volume: -1.0,
-					chapter: -1.0,
-					date_updated: -1.0,
-					scanlator: if scanlator.as_str() == "Unknown" {
-						String::new()
-					} else {
-						scanlator.clone()
-					},
-					url: String::new(),
-					lang: lang.clone(),
-				};
-				for (idx, cell) in cells.enumerate() {
-					let node = cell.as_node();
-					match idx {
-						0 => {
-							chapter.chapter =
-								extract_f32_from_string(String::new(), node.text().read());
-						}
-						1 => {
-							let anchor = node.select("a");
-							chapter.id = anchor.attr("href").read();
-							chapter.title = String::from(anchor.text().read().trim());
-							chapter.url = format!("{}{}", self.base_url, chapter.id);
-						}
-						3 => {
-							chapter.date_updated =
-								convert_time(String::from(node.text().read().trim()));
-						}
-						_ => continue,
-					}
+			// other translations of the title are separate pages, linked with their flag
+			let mut translated: Vec<Chapter> = Vec::new();
+			let mut visited: Vec<String> = Vec::new();
+			for elem in html.select(TRANSLATIONS_SELECTOR).array() {
+				let node = elem.as_node();
+				let translation_id = path_of(&node.attr("href").read());
+				let code = flag_code(&node.select("img.flag").attr("src").read());
+				if translation_id == id
+					|| code.is_empty()
+					|| code == site_lang
+					|| !is_wanted(&code)
+					|| visited.contains(&translation_id)
+				{
+					continue;
 				}
-				chapters.push(chapter);
+				let translation_html = Request::new(
+					format!("{}{translation_id}", self.base_url).as_str(),
+					HttpMethod::Get,
+				)
+				.html();
+				let (_, mut chapters) = self.parse_chapter_table(&translation_html);
+				translated.append(&mut chapters);
+				visited.push(translation_id);
 			}
+
+			// keep the page's own chapters when no selected translation exists
+			let mut chapters = if is_wanted(&site_lang) || translated.is_empty() {
+				main_chapters
+			} else {
+				Vec::new()
+			};
+			chapters.append(&mut translated);
 			Ok(chapters)
-		} else if id.contains("wallpaper") || id.contains("Cosplay") {
+		} else if kind.is_image_set() {
 			let mut chapters: Vec<Chapter> = Vec::with_capacity(1);
 			chapters.push(Chapter {
 				id: format!("{id}/image"),
@@ -429,12 +490,15 @@ impl OtakuSanctuarySource {
 			});
 			Ok(chapters)
 		} else {
-			unreachable!()
+			Err(AidokuError {
+				reason: AidokuErrorKind::Unimplemented,
+			})
 		}
 	}
 
 	pub fn get_page_list(&self, id: String) -> Result<Vec<Page>> {
-		if id.contains("chapter") {
+		let kind = ContentKind::parse(&id)?;
+		if kind == ContentKind::MangaChapter {
 			let resp =
 				Request::new(format!("{}{id}", self.base_url).as_str(), HttpMethod::Get).html();
 			let vi = resp.select("#dataip").attr("value").read();
@@ -470,8 +534,9 @@ impl OtakuSanctuarySource {
 			};
 			let pages = json::parse(raw_pages_arr.as_bytes()).as_array()?;
 			let mut page_arr: Vec<Page> = Vec::with_capacity(pages.len());
+			let mut resolver = ImageResolver::new(vi, get_lang_code(), self.base_url);
 			for (index, page) in pages.enumerate() {
-				let url = url_replacer(page.as_string()?.read(), vi.clone());
+				let url = resolver.resolve(&page.as_string()?.read());
 				page_arr.push(Page {
 					index: index as i32,
 					url,
@@ -480,7 +545,12 @@ impl OtakuSanctuarySource {
 				});
 			}
 			Ok(page_arr)
-		} else if id.contains("wallpaper") || id.contains("Cosplay") {
+		} else if kind == ContentKind::NovelChapter {
+			let html =
+				Request::new(format!("{}{id}", self.base_url).as_str(), HttpMethod::Get).html();
+			let content = html.select("div.chapter-content, div#chapter-content");
+			Ok(text_pages(paragraphs(content)))
+		} else if kind.is_image_set() {
 			let html = Request::new(
 				format!("{}{}", self.base_url, id.replace("/image", "")).as_str(),
 				HttpMethod::Get,
@@ -496,7 +566,9 @@ impl OtakuSanctuarySource {
 			});
 			Ok(page_arr)
 		} else {
-			unreachable!()
+			Err(AidokuError {
+				reason: AidokuErrorKind::Unimplemented,
+			})
 		}
 	}
 
@@ -505,19 +577,14 @@ impl OtakuSanctuarySource {
 	}
 
 	pub fn handle_url(&self, url: String) -> Result<DeepLink> {
-		if url.contains("manga-detail") || url.contains("Cosplay") || url.contains("wallpaper") {
-			let id = String::from(&url[20..]);
-			Ok(DeepLink {
-				manga: Some(self.get_manga_details(id)?),
-				chapter: None,
-			})
-		} else if url.contains("chapter") {
+		let id = path_of(&url);
+		if ContentKind::parse(&id)?.is_chapter() {
 			let resp = Request::new(&url, HttpMethod::Get).html();
 			let breadcrumbs_node = resp.select("a.itemcrumb.active");
-			let manga_id = breadcrumbs_node.attr("href").read();
+			let manga_id = path_of(&breadcrumbs_node.attr("href").read());
 			let manga = Some(self.get_manga_details(manga_id)?);
 			let chapter = Some(Chapter {
-				id: String::from(&url[20..]),
+				id,
 				title: String::new(),
 				volume: -1.0,
 				chapter: -1.0,
@@ -528,7 +595,10 @@ impl OtakuSanctuarySource {
 			});
 			Ok(DeepLink { manga, chapter })
 		} else {
-			unreachable!()
+			Ok(DeepLink {
+				manga: Some(self.get_manga_details(id)?),
+				chapter: None,
+			})
 		}
 	}
 }
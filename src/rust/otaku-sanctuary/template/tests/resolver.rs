@@ -0,0 +1,175 @@
+use otaku_sanctuary_template::resolver::{
+	drive_file_id, expand_tokens, host_of, mirrors, parse_drive_confirm,
+};
+
+const VI: &str = "otakusan.net_xgbKp";
+
+// links of a chapter's `Content` payload, in page order
+fn payload_links() -> Vec<String> {
+	include_str!("fixtures/chapter_content.json")
+		.trim()
+		.trim_start_matches("[\"")
+		.trim_end_matches("\"]")
+		.split("\",\"")
+		.map(String::from)
+		.collect()
+}
+
+#[test]
+fn expands_host_tokens() {
+	let urls = payload_links()
+		.iter()
+		.map(|link| expand_tokens(link, VI))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		urls,
+		[
+			"https://imagepi.otakuscan.net/Manga/31945/2/001.jpg?t=1652081623&vi=otakusan.net_xgbKp",
+			"https://imagepi.otakuscan.net/Manga/31945/2/002.jpg?vi=otakusan.net_xgbKp",
+			"https://image3.otakusan.net/Content/Images/Manga/31945/2/003.jpg",
+			"http://image3.shopotaku.net/Content/Images/Manga/31945/2/004.jpg",
+			"https://drive.google.com/uc?export=view&id=1aBcD3fGh_iJkLmN0pQrStUvWxYz-12",
+			"https://image2.otakuscan.net/Content/Images/Manga/31945/2/006.jpg",
+			"https://image2.otakusan.net/extendContent/Manga/31945/2/007.jpg",
+			"https://i.blogtruyen.vn/uploads/31945/008.jpg",
+		]
+	);
+}
+
+#[test]
+fn adds_vi_to_imagepi_links_without_one() {
+	let url = expand_tokens("[IS1]Manga/1/1/001.jpg?vi=other", VI);
+	assert_eq!(
+		url,
+		"https://imagepi.otakuscan.net/Manga/1/1/001.jpg?vi=other"
+	);
+	let url = expand_tokens("[IS1]Manga/1/1/001.jpg", VI);
+	assert_eq!(
+		url,
+		"https://imagepi.otakuscan.net/Manga/1/1/001.jpg?vi=otakusan.net_xgbKp"
+	);
+}
+
+#[test]
+fn reads_hosts() {
+	let links = payload_links();
+	assert_eq!(
+		host_of(&expand_tokens(&links[0], VI)),
+		"imagepi.otakuscan.net"
+	);
+	assert_eq!(
+		host_of(&expand_tokens(&links[3], VI)),
+		"image3.shopotaku.net"
+	);
+	assert_eq!(
+		host_of(&expand_tokens(&links[5], VI)),
+		"image2.otakuscan.net"
+	);
+	assert_eq!(host_of("image2.otakusan.net/a.jpg"), "image2.otakusan.net");
+	assert_eq!(host_of("https://otakusan.net?page=2"), "otakusan.net");
+	assert_eq!(host_of(""), "");
+}
+
+#[test]
+fn lists_mirrors_after_the_link() {
+	let url = expand_tokens(&payload_links()[5], VI);
+	assert_eq!(
+		mirrors(&url, "vn"),
+		[
+			"https://image2.otakuscan.net/Content/Images/Manga/31945/2/006.jpg",
+			"https://image2.otakusan.net/Content/Images/Manga/31945/2/006.jpg",
+			"https://image3.shopotaku.net/Content/Images/Manga/31945/2/006.jpg",
+		]
+	);
+	let url = expand_tokens(&payload_links()[2], VI);
+	assert_eq!(
+		mirrors(&url, "vn"),
+		[
+			"https://image3.otakusan.net/Content/Images/Manga/31945/2/003.jpg",
+			"https://image3.shopotaku.net/Content/Images/Manga/31945/2/003.jpg",
+		]
+	);
+}
+
+#[test]
+fn tries_the_english_host_first_on_the_english_site() {
+	let url = expand_tokens(&payload_links()[6], VI);
+	assert_eq!(
+		mirrors(&url, "us"),
+		[
+			"https://image3.shopotaku.net/extendContent/Manga/31945/2/007.jpg",
+			"https://image2.otakusan.net/extendContent/Manga/31945/2/007.jpg",
+			"https://image2.otakuscan.net/extendContent/Manga/31945/2/007.jpg",
+		]
+	);
+}
+
+#[test]
+fn has_no_mirrors_for_other_hosts() {
+	let url = expand_tokens(&payload_links()[7], VI);
+	assert_eq!(mirrors(&url, "us"), [url.clone()]);
+}
+
+#[test]
+fn reads_drive_file_ids() {
+	let id = Some(String::from("1aBcD3fGh_iJkLmN0pQrStUvWxYz-12"));
+	let url = expand_tokens(&payload_links()[4], VI);
+	assert_eq!(drive_file_id(&url), id);
+	assert_eq!(
+		drive_file_id(
+			"https://drive.google.com/file/d/1aBcD3fGh_iJkLmN0pQrStUvWxYz-12/view?usp=sharing"
+		),
+		id
+	);
+	assert_eq!(
+		drive_file_id("https://drive.google.com/open?id=1aBcD3fGh_iJkLmN0pQrStUvWxYz-12#page"),
+		id
+	);
+	assert_eq!(
+		drive_file_id("https://drive.google.com/uc?export=view&id="),
+		None
+	);
+	assert_eq!(
+		drive_file_id("https://drive.google.com/drive/my-drive"),
+		None
+	);
+}
+
+// the confirm page fixtures are written after the markup of Drive's warning
+// pages, with a made up file id, uuid and confirm code
+
+#[test]
+fn reads_the_download_form() {
+	let page = include_str!("fixtures/drive_confirm.html");
+	assert_eq!(
+		parse_drive_confirm(page).as_deref(),
+		Some(
+			"https://drive.usercontent.google.com/download\
+			?id=1aBcD3fGh%5FiJkLmN0pQrStUvWxYz%2D12\
+			&export=download\
+			&confirm=t\
+			&uuid=4f0d2c8e%2D6b1a%2D4e3b%2D9c5d%2D7a8e1f2b3c4d"
+		)
+	);
+}
+
+#[test]
+fn reads_the_legacy_download_link() {
+	let page = include_str!("fixtures/drive_confirm_legacy.html");
+	assert_eq!(
+		parse_drive_confirm(page).as_deref(),
+		Some(
+			"https://drive.google.com/uc?export=download&confirm=Xk3q\
+			&id=1aBcD3fGh_iJkLmN0pQrStUvWxYz-12"
+		)
+	);
+}
+
+#[test]
+fn ignores_pages_without_a_download() {
+	assert_eq!(
+		parse_drive_confirm("<html><body>Not found</body></html>"),
+		None
+	);
+	assert_eq!(parse_drive_confirm(""), None);
+}
@@ -4,12 +4,12 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{net::Request, String, Vec},
-	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaPageResult, Page,
+	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 };
 use helper::{convert_time, listing_mapping};
 use wpcomics_template::{
-	helper::{trunc_trailing_comic, urlencode},
-	template::{self, WPComicsSource},
+	helper::trunc_trailing_comic,
+	template::{SearchDescriptor, SearchSelect, WPComicsSource},
 };
 
 fn get_instance() -> WPComicsSource {
@@ -43,47 +43,29 @@ fn get_instance() -> WPComicsSource {
 		manga_viewer_page: "div.chapter-container > img",
 		manga_viewer_page_url_suffix: "/full",
 
+		user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39",
+		search: SearchDescriptor {
+			title_path: "",
+			title_param: "key",
+			path: "advanced-search",
+			genres_param: "wg",
+			excluded_genres_param: "wog",
+			selects: &[SearchSelect {
+				name: "Status",
+				param: "status",
+				values: &["", "ONG", "CMP"],
+			}],
+			browse_path: Some(""),
+			..Default::default()
+		},
+
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut included_tags: Vec<String> = Vec::new();
-	let mut excluded_tags: Vec<String> = Vec::new();
-	let mut title: String = String::new();
-	let mut completed: String = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = urlencode(filter.value.as_string()?.read());
-			}
-			FilterType::Genre => match filter.value.as_int().unwrap_or(-1) {
-				0 => excluded_tags.push(String::from(&filter.name)),
-				1 => included_tags.push(String::from(&filter.name)),
-				_ => continue,
-			},
-			_ => match filter.name.as_str() {
-				"Status" => {
-					let completed_idx = filter.value.as_int().unwrap_or(-1);
-					match completed_idx {
-						1 => completed = String::from("ONG"),
-						2 => completed = String::from("CMP"),
-						_ => continue,
-					}
-				}
-				_ => continue,
-			},
-		}
-	}
-	get_instance().get_manga_list(helper::get_search_url(
-		String::from("https://comiconlinefree.net"),
-		title,
-		included_tags,
-		excluded_tags,
-		completed,
-		page,
-	))
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
@@ -99,7 +81,7 @@ fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
 			listing_mapping(listing.name)
 		)
 	};
-	get_instance().get_manga_list(url)
+	get_instance().get_manga_list_from_url(url)
 }
 
 #[get_manga_details]
@@ -119,11 +101,7 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 
 #[modify_image_request]
 fn modify_image_request(request: Request) {
-	template::modify_image_request(
-		String::from("https://comiconlinefree.net"),
-		String::from("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39"),
-		request,
-	)
+	get_instance().modify_image_request(request)
 }
 
 #[handle_url]
@@ -78,29 +78,6 @@ pub fn status_map(arg1: String) -> MangaStatus {
 }
 
 // MARK: Other utilities
-#[allow(clippy::too_many_arguments)]
-pub fn get_search_url(
-	base_url: String,
-	query: String,
-	page: i32,
-	include: Vec<String>,
-	exclude: Vec<String>,
-	sort_by: i32,
-	gender: i32,
-	completed: i32,
-	chapter_count: i32,
-) -> String {
-	return if !query.is_empty() {
-		format!("{base_url}/tim-truyen?page={page}&keyword={query}")
-	} else {
-		format!(
-			"{base_url}/tim-truyen-nang-cao?genres={}&notgenres={}&gender={gender}&status={completed}&minchapter={chapter_count}&sort={sort_by}&page={page}",
-			include.join(","),
-			exclude.join(",")
-		)
-	};
-}
-
 pub fn convert_time(time_ago: String) -> f64 {
 	let current_time = current_date();
 	let time_arr = time_ago.split(' ').collect::<Vec<&str>>();
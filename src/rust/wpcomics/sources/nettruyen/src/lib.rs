@@ -2,14 +2,13 @@
 pub mod helper;
 use crate::helper::*;
 use aidoku::{
-	error::Result,
-	prelude::*,
-	std::net::Request,
-	std::String,
-	std::{defaults::defaults_get, Vec},
-	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaPageResult, MangaViewer, Page,
+	error::Result, prelude::*, std::net::Request, std::String, std::Vec, Chapter, DeepLink, Filter,
+	Listing, Manga, MangaPageResult, MangaViewer, Page,
+};
+use wpcomics_template::{
+	helper::urlencode,
+	template::{SearchDescriptor, SearchSelect, WPComicsSource},
 };
-use wpcomics_template::{helper::urlencode, template, template::WPComicsSource};
 
 fn get_instance() -> WPComicsSource {
 	WPComicsSource {
@@ -26,14 +25,42 @@ fn get_instance() -> WPComicsSource {
 		},
 		status_mapping: status_map,
 		time_converter: convert_time,
-		page_url_transformer: |url| {
-			let mut server_two = String::from("https://images2-focus-opensocial.googleusercontent.com/gadgets/proxy?container=focus&gadget=a&no_expand=1&resize_h=0&rewriteMime=image%2F*&url=");
-			if let Ok(2) = defaults_get("serverSelection").as_int() {
-				server_two.push_str(&urlencode(url));
-				server_two
-			} else {
-				url
-			}
+		chapter_ajax_path: "Comic/Services/ComicService.asmx/ProcessChapterList?comicId={id}",
+		chapter_ajax_id_selector: "a.follow-link",
+		page_url_fallbacks: |url| {
+			let mut proxy = String::from("https://images2-focus-opensocial.googleusercontent.com/gadgets/proxy?container=focus&gadget=a&no_expand=1&resize_h=0&rewriteMime=image%2F*&url=");
+			proxy.push_str(&urlencode(url));
+			[proxy].to_vec()
+		},
+		server_selection_key: "serverSelection",
+		user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39",
+		search: SearchDescriptor {
+			genre_mapping: get_tag_id,
+			selects: &[
+				SearchSelect {
+					name: "Số lượng chapter",
+					param: "minchapter",
+					values: &["1", "50", "100", "200", "300", "400", "500"],
+				},
+				SearchSelect {
+					name: "Tình trạng",
+					param: "status",
+					values: &["-1", "1", "2"],
+				},
+				SearchSelect {
+					name: "Dành cho",
+					param: "gender",
+					values: &["-1", "1", "2"],
+				},
+				SearchSelect {
+					name: "Sắp xếp theo",
+					param: "sort",
+					// new chapters, new mangas, most watched (all time, month, week, today),
+					// most followed, most commented, most chapters, alphabetical
+					values: &["0", "15", "10", "11", "12", "13", "20", "25", "30", "5"],
+				},
+			],
+			..Default::default()
 		},
 		..Default::default()
 	}
@@ -41,80 +68,7 @@ fn get_instance() -> WPComicsSource {
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut included_tags: Vec<String> = Vec::new();
-	let mut excluded_tags: Vec<String> = Vec::new();
-	let mut title: String = String::new();
-	let mut sort_by: i32 = 0;
-	let mut gender: i32 = -1;
-	let mut completed: i32 = -1;
-	let mut chapter_count: i32 = 0;
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = urlencode(filter.value.as_string()?.read());
-			}
-			FilterType::Genre => match filter.value.as_int().unwrap_or(-1) {
-				0 => excluded_tags.push(get_tag_id(String::from(&filter.name))),
-				1 => included_tags.push(get_tag_id(String::from(&filter.name))),
-				_ => continue,
-			},
-			_ => {
-				match filter.name.as_str() {
-					"Tình trạng" => {
-						completed = filter.value.as_int().unwrap_or(-1) as i32;
-						if completed == 0 {
-							completed = -1;
-						}
-					}
-					"Số lượng chapter" => {
-						chapter_count = match filter.value.as_int().unwrap_or(0) {
-							0 => 1,
-							1 => 50,
-							2 => 100,
-							3 => 200,
-							4 => 300,
-							5 => 400,
-							6 => 500,
-							_ => 1,
-						};
-					}
-					"Sắp xếp theo" => {
-						sort_by = match filter.value.as_int().unwrap_or(0) {
-							0 => 0,  // new chapters
-							1 => 15, // new mangas
-							2 => 10, // most watched
-							3 => 11, // most watched this month
-							4 => 12, // most watched this week
-							5 => 13, // most watched today
-							6 => 20, // most followed
-							7 => 25, // most commented
-							8 => 30, // most chapters
-							9 => 5,  // alphabetical
-							_ => 0,
-						};
-					}
-					"Dành cho" => {
-						gender = filter.value.as_int().unwrap_or(-1) as i32;
-						if gender == 0 {
-							gender = -1;
-						}
-					}
-					_ => continue,
-				}
-			}
-		}
-	}
-	get_instance().get_manga_list(get_search_url(
-		String::from("https://www.nettruyenme.com"),
-		title,
-		page,
-		included_tags,
-		excluded_tags,
-		sort_by,
-		gender,
-		completed,
-		chapter_count,
-	))
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
@@ -139,11 +93,7 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 
 #[modify_image_request]
 fn modify_image_request(request: Request) {
-	template::modify_image_request(
-		String::from("https://www.nettruyenme.com"),
-		String::from("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39"),
-		request,
-	)
+	get_instance().modify_image_request(request)
 }
 
 #[handle_url]
@@ -1,4 +1,4 @@
-use aidoku::{prelude::format, std::String};
+use aidoku::std::String;
 
 pub fn listing_mapping(listing: String) -> String {
 	String::from(match listing.as_str() {
@@ -8,10 +8,3 @@ pub fn listing_mapping(listing: String) -> String {
 		_ => "",
 	})
 }
-
-pub fn get_search_url(base_url: String, genre: String, page: i32) -> String {
-	if !genre.is_empty() {
-		return format!("{base_url}/genre/{genre}?page={page}");
-	}
-	return format!("{base_url}/comic-updates?page={page}");
-}
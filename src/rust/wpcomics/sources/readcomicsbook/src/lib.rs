@@ -7,11 +7,11 @@ use aidoku::{
 	std::{net::HttpMethod, net::Request, String, Vec},
 	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaPageResult, Page,
 };
-use helper::{get_search_url, listing_mapping};
+use helper::listing_mapping;
 use parser::parse_comic;
 use wpcomics_template::{
-	helper::{get_tag_id, urlencode},
-	template::{self, *},
+	helper::{urlencode, COMIC_GENRES},
+	template::*,
 };
 
 fn get_instance() -> WPComicsSource {
@@ -40,27 +40,30 @@ fn get_instance() -> WPComicsSource {
 
 		manga_viewer_page_url_suffix: "/all",
 
+		user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39",
+		search: SearchDescriptor {
+			path_selects: &[SearchSelect {
+				name: "Genre",
+				param: "genre/{value}",
+				values: &COMIC_GENRES,
+			}],
+			browse_path: Some("comic-updates"),
+			..Default::default()
+		},
+
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut title: String = String::new();
-	let mut genre: String = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = urlencode(filter.value.as_string()?.read());
-			}
-			_ => match filter.name.as_str() {
-				"Genre" => {
-					genre = get_tag_id(filter.value.as_int().unwrap_or(0));
-				}
-				_ => continue,
-			},
-		}
-	}
+	// titles are searched through the site's json api
+	let title = filters
+		.iter()
+		.find(|filter| matches!(filter.kind, FilterType::Title))
+		.and_then(|filter| filter.value.clone().as_string().ok())
+		.map(|title| urlencode(title.read()))
+		.unwrap_or_default();
 	if !title.is_empty() {
 		let json = Request::new(
 			format!("https://readcomicsbook.com/ajax/search?q={title}").as_str(),
@@ -81,11 +84,7 @@ fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
 			has_more: false,
 		})
 	} else {
-		get_instance().get_manga_list(get_search_url(
-			String::from("https://readcomicsbook.com"),
-			genre,
-			page,
-		))
+		get_instance().get_manga_list(filters, page)
 	}
 }
 
@@ -111,11 +110,7 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 
 #[modify_image_request]
 fn modify_image_request(request: Request) {
-	template::modify_image_request(
-		String::from("https://readcomicsbook.com"),
-		String::from("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39"),
-		request,
-	)
+	get_instance().modify_image_request(request)
 }
 
 #[handle_url]
@@ -3,11 +3,11 @@ pub mod helper;
 use crate::helper::*;
 use aidoku::{
 	error::Result, prelude::*, std::net::Request, std::String, std::Vec, Chapter, DeepLink, Filter,
-	FilterType, Listing, Manga, MangaPageResult, Page,
+	Listing, Manga, MangaPageResult, Page,
 };
 use wpcomics_template::{
-	helper::{get_tag_id, urlencode},
-	template::WPComicsSource,
+	helper::COMIC_GENRES,
+	template::{SearchDescriptor, SearchSelect, WPComicsSource},
 };
 
 fn get_instance() -> WPComicsSource {
@@ -23,33 +23,24 @@ fn get_instance() -> WPComicsSource {
 		chapter_date_selector: "div.col-xs-3",
 
 		manga_viewer_page_url_suffix: "/all",
+
+		search: SearchDescriptor {
+			title_path: "search",
+			path_selects: &[SearchSelect {
+				name: "Genre",
+				param: "genre/{value}",
+				values: &COMIC_GENRES,
+			}],
+			browse_path: Some(""),
+			..Default::default()
+		},
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut title: String = String::new();
-	let mut genre: String = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = urlencode(filter.value.as_string()?.read());
-			}
-			_ => match filter.name.as_str() {
-				"Genre" => {
-					genre = get_tag_id(filter.value.as_int().unwrap_or(0));
-				}
-				_ => continue,
-			},
-		}
-	}
-	get_instance().get_manga_list(get_search_url(
-		String::from("https://readcomicsfree.com"),
-		title,
-		genre,
-		page,
-	))
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
@@ -2,11 +2,13 @@
 use aidoku::{
 	error::Result,
 	prelude::*,
-	std::{defaults::defaults_get, net::Request, String, StringRef, Vec},
-	Chapter, DeepLink, Filter, FilterType, Listing, Manga, MangaPageResult, MangaStatus,
-	MangaViewer, Page,
+	std::{net::Request, String, StringRef, Vec},
+	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, MangaStatus, MangaViewer, Page,
+};
+use wpcomics_template::{
+	helper::urlencode,
+	template::{SearchDescriptor, SearchSelect, WPComicsSource},
 };
-use wpcomics_template::{helper::urlencode, template::WPComicsSource};
 
 fn get_instance() -> WPComicsSource {
 	WPComicsSource {
@@ -54,107 +56,51 @@ fn get_instance() -> WPComicsSource {
 		chapter_anchor_selector: "div.name-chap a",
 		chapter_date_selector: "div.time-chap",
 
-		page_url_transformer: |url| {
-			let mut server_two = String::from("https://images2-focus-opensocial.googleusercontent.com/gadgets/proxy?container=focus&gadget=a&no_expand=1&resize_h=0&rewriteMime=image%2F*&url=");
-			if let Ok(2) = defaults_get("serverSelection").as_int() {
-				server_two.push_str(&urlencode(url));
-				server_two
-			} else {
-				url
-			}
+		page_url_fallbacks: |url| {
+			let mut proxy = String::from("https://images2-focus-opensocial.googleusercontent.com/gadgets/proxy?container=focus&gadget=a&no_expand=1&resize_h=0&rewriteMime=image%2F*&url=");
+			proxy.push_str(&urlencode(url));
+			[proxy].to_vec()
 		},
+		server_selection_key: "serverSelection",
 		vinahost_protection: true,
+		search: SearchDescriptor {
+			title_path: "tim-kiem/trang-{page}.html",
+			title_param: "q",
+			path: "tim-kiem-nang-cao.html",
+			page_param: "",
+			genres_param: "category",
+			excluded_genres_param: "notcategory",
+			selects: &[
+				SearchSelect {
+					name: "Tình trạng",
+					param: "status",
+					values: &["-1", "1", "2"],
+				},
+				SearchSelect {
+					name: "Quốc gia",
+					param: "country",
+					values: &["0", "1", "2", "3", "4", "5"],
+				},
+				SearchSelect {
+					name: "Số lượng chapter",
+					param: "minchapter",
+					values: &["0", "50", "100", "200", "300", "400", "500"],
+				},
+				SearchSelect {
+					name: "Sắp xếp theo",
+					param: "sort",
+					values: &["0", "1", "2", "3", "4", "5"],
+				},
+			],
+			..Default::default()
+		},
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	fn get_search_url(filters: Vec<Filter>, page: i32) -> String {
-		let mut excluded_tags: Vec<String> = Vec::new();
-		let mut included_tags: Vec<String> = Vec::new();
-		let mut query = String::new();
-		for filter in filters {
-			match filter.kind {
-				FilterType::Title => {
-					let title = urlencode(
-						filter
-							.value
-							.as_string()
-							.unwrap_or_else(|_| StringRef::from(""))
-							.read()
-					);
-					if !title.is_empty() {
-						return format!(
-							"http://truyenqqpro.com/tim-kiem/trang-{page}.html?q={title}"
-						);
-					}
-				}
-				FilterType::Genre => {
-					let genre = filter
-						.object
-						.get("id")
-						.as_string()
-						.unwrap_or_else(|_| StringRef::from(""))
-						.read();
-					if genre.is_empty() {
-						continue;
-					}
-					match filter.value.as_int().unwrap_or(-1) {
-						0 => excluded_tags.push(genre),
-						1 => included_tags.push(genre),
-						_ => continue,
-					}
-				}
-				_ => match filter.name.as_str() {
-					"Tình trạng" => {
-						let mut status = filter.value.as_int().unwrap_or(-1);
-						if status == 0 {
-							status = -1
-						}
-						query.push_str("&status=");
-						query.push_str(format!("{}", status).as_str());
-					}
-					"Quốc gia" => {
-						let country = filter.value.as_int().unwrap_or(-1);
-						if country >= 0 {
-							query.push_str("&country=");
-							query.push_str(format!("{}", country).as_str());
-						}
-					}
-					"Số lượng chapter" => {
-						let minchapter = match filter.value.as_int().unwrap_or(-1) {
-							0 => "0",
-							1 => "50",
-							2 => "100",
-							3 => "200",
-							4 => "300",
-							5 => "400",
-							6 => "500",
-							_ => continue,
-						};
-						query.push_str("&minchapter=");
-						query.push_str(minchapter);
-					}
-					"Sắp xếp theo" => {
-						let sort = filter.value.as_int().unwrap_or(-1);
-						if sort >= 0 {
-							query.push_str("&sort=");
-							query.push_str(format!("{}", sort).as_str());
-						}
-					}
-					_ => continue,
-				},
-			}
-		}
-		format!(
-			"http://truyenqqpro.com/tim-kiem-nang-cao.html?category={}&notcategory={}{}",
-			included_tags.join(","),
-			excluded_tags.join(","),
-			query
-		)
-	}
-	get_instance().get_manga_list(get_search_url(filters, page))
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
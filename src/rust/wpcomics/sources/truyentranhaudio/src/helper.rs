@@ -33,60 +33,58 @@ pub fn convert_time(time_ago: String) -> f64 {
 	}
 }
 
-pub fn genre_mapping(genre: i64) -> String {
-	String::from(match genre {
-		0 => "",
-		1 => "action",
-		2 => "adult",
-		3 => "adventure",
-		4 => "anime",
-		5 => "chuyen-sinh",
-		6 => "comedy",
-		7 => "comic",
-		8 => "cooking",
-		9 => "co-dai",
-		10 => "doujinshi",
-		11 => "drama",
-		12 => "dam-my",
-		13 => "dam-my",
-		14 => "ecchi",
-		15 => "fantasy",
-		16 => "gender-bender",
-		17 => "harem",
-		18 => "historical",
-		19 => "horror",
-		20 => "josei",
-		21 => "live-action",
-		22 => "manga",
-		23 => "manhua",
-		24 => "manhwa",
-		25 => "martial-arts",
-		26 => "martial-arts",
-		27 => "mature",
-		28 => "mecha",
-		29 => "mystery",
-		30 => "ngon-tinh",
-		31 => "one-shot",
-		32 => "psychological",
-		33 => "romance",
-		34 => "school-life",
-		35 => "sci-fi",
-		36 => "seinen",
-		37 => "shoujo",
-		38 => "shoujo-ai",
-		39 => "shoujo-ai",
-		40 => "shounen",
-		41 => "shounen-ai",
-		42 => "slice-of-life",
-		43 => "smut",
-		44 => "soft-yaoi",
-		45 => "soft-yuri",
-		46 => "sports",
-		47 => "supernatural",
-		48 => "tragedy",
-		49 => "xuyen-khong",
-		50 => "webtoon",
-		51 => "truyen-mau",
-		_ => "",
-	})
-}
+// genre ids in the order of the "Thể loại" select options
+pub const GENRE_IDS: &[&str] = &[
+	"",
+	"action",
+	"adult",
+	"adventure",
+	"anime",
+	"chuyen-sinh",
+	"comedy",
+	"comic",
+	"cooking",
+	"co-dai",
+	"doujinshi",
+	"drama",
+	"dam-my",
+	"dam-my",
+	"ecchi",
+	"fantasy",
+	"gender-bender",
+	"harem",
+	"historical",
+	"horror",
+	"josei",
+	"live-action",
+	"manga",
+	"manhua",
+	"manhwa",
+	"martial-arts",
+	"martial-arts",
+	"mature",
+	"mecha",
+	"mystery",
+	"ngon-tinh",
+	"one-shot",
+	"psychological",
+	"romance",
+	"school-life",
+	"sci-fi",
+	"seinen",
+	"shoujo",
+	"shoujo-ai",
+	"shoujo-ai",
+	"shounen",
+	"shounen-ai",
+	"slice-of-life",
+	"smut",
+	"soft-yaoi",
+	"soft-yuri",
+	"sports",
+	"supernatural",
+	"tragedy",
+	"xuyen-khong",
+	"webtoon",
+	"truyen-mau",
+];
@@ -5,9 +5,9 @@ use aidoku::{
 	error::Result,
 	prelude::*,
 	std::{defaults::defaults_get, net::Request, String, StringRef, Vec},
-	Chapter, DeepLink, Filter, FilterType, Manga, MangaPageResult, MangaStatus, MangaViewer, Page,
+	Chapter, DeepLink, Filter, Manga, MangaPageResult, MangaStatus, MangaViewer, Page,
 };
-use wpcomics_template::{helper::urlencode, template::WPComicsSource};
+use wpcomics_template::template::{SearchDescriptor, SearchSelect, WPComicsSource};
 
 fn get_instance() -> WPComicsSource {
 	let base_url = defaults_get("sourceURL")
@@ -25,63 +25,37 @@ fn get_instance() -> WPComicsSource {
 		},
 		next_page: "li > a:contains(Cuối »)",
 		manga_cell_image: "div.image img",
+		search: SearchDescriptor {
+			title_path: "",
+			path: "tim-truyen",
+			genres_param: "",
+			excluded_genres_param: "",
+			selects: &[
+				SearchSelect {
+					name: "Tình trạng",
+					param: "status",
+					values: &["", "1", "2"],
+				},
+				SearchSelect {
+					name: "Sắp xếp theo",
+					param: "sort",
+					values: &["", "15", "10", "11", "12", "13"],
+				},
+				SearchSelect {
+					name: "Thể loại",
+					param: "genre",
+					values: GENRE_IDS,
+				},
+			],
+			..Default::default()
+		},
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut title = String::new();
-	let mut status: i64 = -1;
-	let mut sort: i64 = -1;
-	let mut genre = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => title = urlencode(filter.value.as_string()?.read()),
-			FilterType::Select => {
-				let value = filter.value.as_int().unwrap_or(-1);
-				if value < 0 {
-					continue;
-				}
-				match filter.name.as_str() {
-					"Tình trạng" => status = value,
-					"Sắp xếp theo" => {
-						sort = match value {
-							0 => 0,
-							1 => 15,
-							2 => 10,
-							3 => 11,
-							4 => 12,
-							5 => 13,
-							_ => -1,
-						}
-					}
-					"Thể loại" => genre = genre_mapping(value),
-					_ => continue,
-				}
-			}
-			_ => continue,
-		}
-	}
-	let mut url = format!(
-		"{}/tim-truyen?genre={}&keyword={}&page={}",
-		defaults_get("sourceURL")
-			.as_string()
-			.unwrap_or_else(|_| StringRef::from(""))
-			.read(),
-		genre,
-		title,
-		page,
-	);
-	if status > 0 {
-		url.push_str("&status=");
-		url.push_str(format!("{}", status).as_str());
-	}
-	if sort > 0 {
-		url.push_str("&sort=");
-		url.push_str(format!("{}", sort).as_str());
-	}
-	get_instance().get_manga_list(url)
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_details]
@@ -1,4 +1,4 @@
-use aidoku::{prelude::format, std::String};
+use aidoku::std::String;
 
 pub fn listing_map(listing: String) -> String {
 	let url: &str = match listing.as_str() {
@@ -10,14 +10,3 @@ pub fn listing_map(listing: String) -> String {
 	};
 	String::from(url)
 }
-
-// MARK: Other utilities
-pub fn get_search_url(base_url: String, query: String, genre: String, page: i32) -> String {
-	return if !query.is_empty() {
-		format!("{base_url}/search?page={page}&keyword={query}")
-	} else if !genre.is_empty() {
-		format!("{base_url}/genre/{genre}?page={page}")
-	} else {
-		base_url
-	};
-}
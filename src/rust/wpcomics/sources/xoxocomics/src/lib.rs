@@ -3,11 +3,11 @@ pub mod helper;
 use crate::helper::*;
 use aidoku::{
 	error::Result, prelude::*, std::net::Request, std::String, std::Vec, Chapter, DeepLink, Filter,
-	FilterType, Listing, Manga, MangaPageResult, Page,
+	Listing, Manga, MangaPageResult, Page,
 };
 use wpcomics_template::{
-	helper::{get_tag_id, trunc_trailing_comic, urlencode},
-	template::{self, WPComicsSource},
+	helper::{trunc_trailing_comic, COMIC_GENRES},
+	template::{SearchDescriptor, SearchSelect, WPComicsSource},
 };
 
 fn get_instance() -> WPComicsSource {
@@ -21,33 +21,25 @@ fn get_instance() -> WPComicsSource {
 		chapter_date_selector: "div.col-xs-3",
 		manga_viewer_page_url_suffix: "/all",
 
+		user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39",
+		search: SearchDescriptor {
+			title_path: "search",
+			path_selects: &[SearchSelect {
+				name: "Genre",
+				param: "genre/{value}",
+				values: &COMIC_GENRES,
+			}],
+			browse_path: Some(""),
+			..Default::default()
+		},
+
 		..Default::default()
 	}
 }
 
 #[get_manga_list]
 fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
-	let mut title: String = String::new();
-	let mut genre: String = String::new();
-	for filter in filters {
-		match filter.kind {
-			FilterType::Title => {
-				title = urlencode(filter.value.as_string()?.read());
-			}
-			_ => match filter.name.as_str() {
-				"Genre" => {
-					genre = get_tag_id(filter.value.as_int().unwrap_or(0));
-				}
-				_ => continue,
-			},
-		}
-	}
-	get_instance().get_manga_list(get_search_url(
-		String::from("https://xoxocomics.com"),
-		title,
-		genre,
-		page,
-	))
+	get_instance().get_manga_list(filters, page)
 }
 
 #[get_manga_listing]
@@ -72,11 +64,7 @@ fn get_page_list(id: String) -> Result<Vec<Page>> {
 
 #[modify_image_request]
 fn modify_image_request(request: Request) {
-	template::modify_image_request(
-		String::from("https://xoxocomics.com"),
-		String::from("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/101.0.4951.54 Safari/537.36 Edg/101.0.1210.39"),
-		request,
-	)
+	get_instance().modify_image_request(request)
 }
 
 #[handle_url]
@@ -55,65 +55,66 @@ pub fn urlencode(string: String) -> String {
 	String::from_utf8(result).unwrap_or_default()
 }
 
-pub fn get_tag_id(genre: i64) -> String {
-	String::from(match genre {
-		1 => "marvel",
-		2 => "dc-comics",
-		3 => "action",
-		4 => "adventure",
-		5 => "anthology",
-		6 => "anthropomorphic",
-		7 => "biography",
-		8 => "children",
-		9 => "comedy",
-		10 => "crime",
-		11 => "cyborgs",
-		12 => "dark-horse",
-		13 => "demons",
-		14 => "drama",
-		15 => "fantasy",
-		16 => "family",
-		17 => "fighting",
-		18 => "gore",
-		19 => "graphic-novels",
-		20 => "historical",
-		21 => "horror",
-		22 => "leading-ladies",
-		23 => "literature",
-		24 => "magic",
-		25 => "manga",
-		26 => "martial-arts",
-		27 => "mature",
-		28 => "mecha",
-		29 => "military",
-		30 => "movie-cinematic-link",
-		31 => "mystery",
-		32 => "mythology",
-		33 => "psychological",
-		34 => "personal",
-		35 => "political",
-		36 => "post-apocalyptic",
-		37 => "pulp",
-		38 => "robots",
-		39 => "romance",
-		40 => "sci-fi",
-		41 => "slice-of-life",
-		42 => "science-fiction",
-		43 => "sport",
-		44 => "spy",
-		45 => "superhero",
-		46 => "supernatural",
-		47 => "suspense",
-		48 => "thriller",
-		49 => "vampires",
-		50 => "vertigo",
-		51 => "video-games",
-		52 => "war",
-		53 => "western",
-		54 => "zombies",
-		_ => "",
-	})
-}
+// genre slugs of the comic sites, in the order of the `Genre` select
+// options of filters.json
+pub const COMIC_GENRES: [&str; 56] = [
+	"",
+	"marvel",
+	"dc-comics",
+	"action",
+	"adventure",
+	"anthology",
+	"anthropomorphic",
+	"biography",
+	"children",
+	"comedy",
+	"crime",
+	"cyborgs",
+	"dark-horse",
+	"demons",
+	"drama",
+	"fantasy",
+	"family",
+	"fighting",
+	"gore",
+	"graphic-novels",
+	"historical",
+	"horror",
+	"leading-ladies",
+	"literature",
+	"magic",
+	"manga",
+	"martial-arts",
+	"mature",
+	"mecha",
+	"military",
+	"movie-cinematic-link",
+	"mystery",
+	"mythology",
+	"psychological",
+	"personal",
+	"political",
+	"post-apocalyptic",
+	"pulp",
+	"robots",
+	"romance",
+	"sci-fi",
+	"slice-of-life",
+	"science-fiction",
+	"sport",
+	"spy",
+	"superhero",
+	"supernatural",
+	"suspense",
+	"thriller",
+	"tragedy",
+	"vampires",
+	"vertigo",
+	"video-games",
+	"war",
+	"western",
+	"zombies",
+];
 
 pub fn text_with_newlines(node: Node) -> String {
 	let html = node.html().read();
@@ -1,3 +1,4 @@
 #![no_std]
+extern crate alloc;
 pub mod helper;
 pub mod template;
@@ -1,10 +1,59 @@
 use aidoku::{
-	error::Result, prelude::*, std::html::Node, std::net::HttpMethod, std::net::Request,
-	std::String, std::StringRef, std::Vec, Chapter, DeepLink, Listing, Manga, MangaContentRating,
-	MangaPageResult, MangaStatus, MangaViewer, Page,
+	error::Result, prelude::*, std::defaults::defaults_get, std::html::Node, std::json::parse,
+	std::net::HttpMethod, std::net::Request, std::String, std::StringRef, std::Vec, Chapter,
+	DeepLink, Filter, FilterType, Listing, Manga, MangaContentRating, MangaPageResult, MangaStatus,
+	MangaViewer, Page,
 };
 
-use crate::helper::{append_protocol, extract_f32_from_string, text_with_newlines};
+use alloc::collections::BTreeSet;
+use common::image::{image_available, request_url, set_request_url};
+
+use crate::helper::{append_protocol, extract_f32_from_string, text_with_newlines, urlencode};
+
+// a select filter from filters.json sent as a query parameter
+pub struct SearchSelect {
+	pub name: &'static str,
+	pub param: &'static str,
+	// value sent for each option index, an empty string leaves the parameter out
+	pub values: &'static [&'static str],
+}
+
+// describes the site's advanced search page, `{page}` in a path is replaced
+// with the page number
+pub struct SearchDescriptor {
+	// a title search uses this page instead of the advanced one when set
+	pub title_path: &'static str,
+	pub title_param: &'static str,
+	pub path: &'static str,
+	pub page_param: &'static str,
+	pub genres_param: &'static str,
+	pub excluded_genres_param: &'static str,
+	// genre filter name to id, used when the filter has no `id` in filters.json
+	pub genre_mapping: fn(String) -> String,
+	pub selects: &'static [SearchSelect],
+	// selects searched through a page of their own instead of a parameter,
+	// `param` is then a path where `{value}` is replaced with the picked value
+	pub path_selects: &'static [SearchSelect],
+	// page listed when no filter is set, the search page itself when `None`
+	pub browse_path: Option<&'static str>,
+}
+
+impl Default for SearchDescriptor {
+	fn default() -> SearchDescriptor {
+		SearchDescriptor {
+			title_path: "tim-truyen",
+			title_param: "keyword",
+			path: "tim-truyen-nang-cao",
+			page_param: "page",
+			genres_param: "genres",
+			excluded_genres_param: "notgenres",
+			genre_mapping: |str| str,
+			selects: &[],
+			path_selects: &[],
+			browse_path: None,
+		}
+	}
+}
 
 pub struct WPComicsSource {
 	pub base_url: String,
@@ -40,17 +89,51 @@ pub struct WPComicsSource {
 	pub chapter_skip_first: bool,
 	pub chapter_date_selector: &'static str,
 	pub chapter_anchor_selector: &'static str,
+	// link to the next chunk of the chapter list ("Xem thêm" style pagination)
+	pub chapter_load_more: &'static str,
+	// endpoint serving the chapters missing from the details page, `{id}` is
+	// the comic id read from `chapter_ajax_id_attr` of `chapter_ajax_id_selector`
+	// and `{page}` (optional) the page number
+	pub chapter_ajax_path: &'static str,
+	pub chapter_ajax_id_selector: &'static str,
+	pub chapter_ajax_id_attr: &'static str,
+	// chapter rows in the html the endpoint returns
+	pub chapter_ajax_selector: &'static str,
+	// chapters the details page renders at most, the endpoint is only asked
+	// for the rest when that many are shown
+	pub chapter_ajax_after: usize,
 
 	pub manga_viewer_page: &'static str,
+	// attributes holding the image url of each server, in order of preference
+	pub manga_viewer_page_attrs: &'static [&'static str],
 	pub manga_viewer_page_url_suffix: &'static str,
 	pub page_url_transformer: fn(String) -> String,
+	// extra candidates (e.g. an image proxy) tried after every server failed
+	pub page_url_fallbacks: fn(String) -> Vec<String>,
+	// settings key of a select picking the server tried first, `1` for the
+	// site's own servers and `2` for `page_url_fallbacks`
+	pub server_selection_key: &'static str,
+	// user agent sent with image requests, the app's own when empty
+	pub user_agent: &'static str,
 
 	pub vinahost_protection: bool,
+
+	pub search: SearchDescriptor,
 }
 
 static mut CACHED_MANGA_ID: Option<String> = None;
 static mut CACHED_MANGA: Option<Vec<u8>> = None;
 static mut VINAHOST_COOKIE: Option<String> = None;
+// server that last worked, with the server setting it was found under
+static mut PREFERRED_SERVER: Option<(i64, usize)> = None;
+// candidates of the pages handed out that have more than one server, checked
+// by `modify_image_request` when the app loads them
+static mut PAGE_CANDIDATES: Vec<Vec<Option<String>>> = Vec::new();
+
+// upper bound on the chapter list pages followed for one manga
+const MAX_CHAPTER_PAGES: i32 = 50;
+// pages whose candidates are kept, a few chapters worth
+const MAX_PAGE_CANDIDATES: usize = 500;
 
 fn cache_manga_page(data: &WPComicsSource, url: &str) {
 	if unsafe { CACHED_MANGA_ID.is_some() } && unsafe { CACHED_MANGA_ID.clone().unwrap() } == url {
@@ -58,41 +141,41 @@ fn cache_manga_page(data: &WPComicsSource, url: &str) {
 	}
 
 	unsafe {
-		if data.vinahost_protection {
-			CACHED_MANGA = Some(data.request_vinahost(url).data());
-		} else {
-			CACHED_MANGA = Some(Request::new(url, HttpMethod::Get).data());
-		}
+		CACHED_MANGA = Some(data.request_vinahost(url));
 		CACHED_MANGA_ID = Some(String::from(url));
 	};
 }
 
 impl WPComicsSource {
-	fn request_vinahost(&self, url: &str) -> Request {
-		if unsafe { VINAHOST_COOKIE.is_some() } {
-			Request::new(url, HttpMethod::Get).header(
-				"Cookie",
-				unsafe { VINAHOST_COOKIE.clone().unwrap() }.as_str(),
-			)
-		} else if self.vinahost_protection {
-			let blocked_html = Request::new(url, HttpMethod::Get).html();
-			let script = blocked_html.select("script").html().read();
-			let cookie = script
-				.replace("document.cookie=\"", "")
-				.replace("\";window.location.reload(true);", "")
-				.replace("\"+\"", "");
-			unsafe {
-				VINAHOST_COOKIE = Some(cookie);
-			};
-			Request::new(url, HttpMethod::Get).header(
-				"Cookie",
-				unsafe { VINAHOST_COOKIE.clone().unwrap() }.as_str(),
-			)
-		} else {
-			Request::new(url, HttpMethod::Get)
+	fn request_with_cookie(&self, url: &str) -> Vec<u8> {
+		match unsafe { VINAHOST_COOKIE.clone() } {
+			Some(cookie) => Request::new(url, HttpMethod::Get)
+				.header("Cookie", &cookie)
+				.data(),
+			None => Request::new(url, HttpMethod::Get).data(),
 		}
 	}
 
+	// fetch a page behind the vinahost cookie challenge, solving it again
+	// whenever it shows up since that means the stored cookie expired
+	fn request_vinahost(&self, url: &str) -> Vec<u8> {
+		if !self.vinahost_protection {
+			return Request::new(url, HttpMethod::Get).data();
+		}
+		let data = self.request_with_cookie(url);
+		let cookie = match core::str::from_utf8(&data)
+			.ok()
+			.and_then(cookie_challenge::solve)
+		{
+			Some(cookie) => cookie,
+			None => return data,
+		};
+		unsafe {
+			VINAHOST_COOKIE = Some(cookie);
+		};
+		self.request_with_cookie(url)
+	}
+
 	fn category_parser(&self, categories: &Vec<String>) -> (MangaContentRating, MangaViewer) {
 		#[allow(clippy::needless_match)]
 		let mut nsfw = match self.nsfw {
@@ -124,9 +207,121 @@ impl WPComicsSource {
 		(nsfw, viewer)
 	}
 
-	pub fn get_manga_list(&self, search_url: String) -> Result<MangaPageResult> {
+	pub fn get_search_url(&self, filters: Vec<Filter>, page: i32) -> String {
+		let search = &self.search;
+		let mut title = String::new();
+		let mut included_tags: Vec<String> = Vec::new();
+		let mut excluded_tags: Vec<String> = Vec::new();
+		let mut params: Vec<String> = Vec::new();
+		let mut select_path: Option<String> = None;
+		for filter in filters {
+			match filter.kind {
+				FilterType::Title => {
+					title = urlencode(
+						filter
+							.value
+							.as_string()
+							.map(|v| v.read())
+							.unwrap_or_default(),
+					);
+				}
+				FilterType::Genre => {
+					let id = match filter.object.get("id").as_string() {
+						Ok(id) => id.read(),
+						Err(_) => (search.genre_mapping)(String::from(&filter.name)),
+					};
+					if id.is_empty() {
+						continue;
+					}
+					match filter.value.as_int().unwrap_or(-1) {
+						0 => excluded_tags.push(urlencode(id)),
+						1 => included_tags.push(urlencode(id)),
+						_ => continue,
+					}
+				}
+				FilterType::Select => {
+					let index = filter.value.as_int().unwrap_or(-1);
+					if index < 0 {
+						continue;
+					}
+					let value = |select: &SearchSelect| {
+						select
+							.values
+							.get(index as usize)
+							.filter(|value| !value.is_empty())
+							.copied()
+					};
+					if let Some(select) = search.selects.iter().find(|s| s.name == filter.name) {
+						if let Some(value) = value(select) {
+							params.push(format!("{}={value}", select.param));
+						}
+					} else if let Some(select) =
+						search.path_selects.iter().find(|s| s.name == filter.name)
+					{
+						if let Some(value) = value(select) {
+							select_path = Some(select.param.replace("{value}", value));
+						}
+					}
+				}
+				_ => continue,
+			}
+		}
+
+		let page_string = format!("{page}");
+		let is_empty = title.is_empty()
+			&& included_tags.is_empty()
+			&& excluded_tags.is_empty()
+			&& params.is_empty()
+			&& select_path.is_none();
+		let path = if !title.is_empty() && !search.title_path.is_empty() {
+			params.clear();
+			params.push(format!("{}={title}", search.title_param));
+			String::from(search.title_path)
+		} else if let Some(select_path) = select_path {
+			select_path
+		} else if let (true, Some(browse_path)) = (is_empty, search.browse_path) {
+			String::from(browse_path)
+		} else {
+			let mut query: Vec<String> = Vec::new();
+			if !search.genres_param.is_empty() {
+				query.push(format!(
+					"{}={}",
+					search.genres_param,
+					included_tags.join(",")
+				));
+			}
+			if !search.excluded_genres_param.is_empty() {
+				query.push(format!(
+					"{}={}",
+					search.excluded_genres_param,
+					excluded_tags.join(",")
+				));
+			}
+			if !title.is_empty() {
+				query.push(format!("{}={title}", search.title_param));
+			}
+			query.append(&mut params);
+			params = query;
+			String::from(search.path)
+		};
+		if !search.page_param.is_empty() && !path.contains("{page}") {
+			params.push(format!("{}={page}", search.page_param));
+		}
+		let mut url = format!("{}/{}", self.base_url, path.replace("{page}", &page_string));
+		if !params.is_empty() {
+			url.push(if url.contains('?') { '&' } else { '?' });
+			url.push_str(&params.join("&"));
+		}
+		url
+	}
+
+	pub fn get_manga_list(&self, filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
+		self.get_manga_list_from_url(self.get_search_url(filters, page))
+	}
+
+	pub fn get_manga_list_from_url(&self, search_url: String) -> Result<MangaPageResult> {
 		let mut has_next_page = !self.next_page.is_empty();
-		let html = self.request_vinahost(&search_url).html();
+		let html = Node::new(&self.request_vinahost(&search_url));
 		let node = html.select(self.manga_cell);
 		let elems = node.array();
 		let mut mangas: Vec<Manga> = Vec::with_capacity(elems.len());
@@ -187,7 +382,7 @@ impl WPComicsSource {
 			self.manga_listing_pagination,
 			self.manga_listing_extension
 		);
-		self.get_manga_list(url)
+		self.get_manga_list_from_url(url)
 	}
 
 	pub fn get_manga_details(&self, id: String) -> Result<Manga> {
@@ -238,55 +433,66 @@ impl WPComicsSource {
 		})
 	}
 
-	pub fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>> {
-		let mut skipped_first = false;
-		let mut chapters: Vec<Chapter> = Vec::new();
-		cache_manga_page(self, id.as_str());
-		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
-		let title_untrimmed = (self.manga_details_title_transformer)(
-			html.select(self.manga_details_title).text().read(),
-		);
-		let title = title_untrimmed.trim();
-		for chapter in html.select(self.manga_details_chapters).array() {
-			if self.chapter_skip_first && !skipped_first {
-				skipped_first = true;
-				continue;
+	fn parse_chapter(
+		&self,
+		manga_title: &str,
+		mut chapter_url: String,
+		mut chapter_title: String,
+		date_updated: f64,
+	) -> Chapter {
+		if !chapter_url.contains("http://") && !chapter_url.contains("https://") {
+			chapter_url = format!("{}/{}", self.base_url, chapter_url.trim_start_matches('/'));
+		}
+		let chapter_id = chapter_url.clone();
+		let numbers =
+			extract_f32_from_string(String::from(manga_title), String::from(&chapter_title));
+		let (volume, chapter) =
+			if numbers.len() > 1 && chapter_title.to_ascii_lowercase().contains("vol") {
+				(numbers[0], numbers[1])
+			} else if !numbers.is_empty() {
+				(-1.0, numbers[0])
+			} else {
+				(-1.0, -1.0)
+			};
+		if chapter >= 0.0 {
+			let splitter = format!(" {}", chapter);
+			let splitter2 = format!("#{}", chapter);
+			if chapter_title.contains(&splitter) {
+				let split = chapter_title.splitn(2, &splitter).collect::<Vec<&str>>();
+				chapter_title =
+					String::from(split[1]).replacen(|char| char == ':' || char == '-', "", 1);
+			} else if chapter_title.contains(&splitter2) {
+				let split = chapter_title.splitn(2, &splitter2).collect::<Vec<&str>>();
+				chapter_title =
+					String::from(split[1]).replacen(|char| char == ':' || char == '-', "", 1);
 			}
+		}
+		Chapter {
+			id: chapter_id,
+			title: String::from(chapter_title.trim()),
+			volume,
+			chapter,
+			date_updated,
+			scanlator: String::new(),
+			url: chapter_url,
+			lang: String::from("en"),
+		}
+	}
+
+	fn parse_chapter_nodes(
+		&self,
+		manga_title: &str,
+		html: &Node,
+		selector: &str,
+		skip_first: bool,
+	) -> Vec<Chapter> {
+		let mut chapters: Vec<Chapter> = Vec::new();
+		for chapter in html.select(selector).array().skip(skip_first as usize) {
 			let chapter_node = chapter.as_node();
-			let mut chapter_url = chapter_node
-				.select(self.chapter_anchor_selector)
-				.attr("href")
-				.read();
-			if !chapter_url.contains("http://") && !chapter_url.contains("https://") {
-				chapter_url = format!("{}/{}", self.base_url, chapter_url);
-			}
-			let chapter_id = chapter_url.clone();
-			let mut chapter_title = chapter_node
-				.select(self.chapter_anchor_selector)
-				.text()
-				.read();
-			let numbers =
-				extract_f32_from_string(String::from(title), String::from(&chapter_title));
-			let (volume, chapter) =
-				if numbers.len() > 1 && chapter_title.to_ascii_lowercase().contains("vol") {
-					(numbers[0], numbers[1])
-				} else if !numbers.is_empty() {
-					(-1.0, numbers[0])
-				} else {
-					(-1.0, -1.0)
-				};
-			if chapter >= 0.0 {
-				let splitter = format!(" {}", chapter);
-				let splitter2 = format!("#{}", chapter);
-				if chapter_title.contains(&splitter) {
-					let split = chapter_title.splitn(2, &splitter).collect::<Vec<&str>>();
-					chapter_title =
-						String::from(split[1]).replacen(|char| char == ':' || char == '-', "", 1);
-				} else if chapter_title.contains(&splitter2) {
-					let split = chapter_title.splitn(2, &splitter2).collect::<Vec<&str>>();
-					chapter_title =
-						String::from(split[1]).replacen(|char| char == ':' || char == '-', "", 1);
-				}
+			let anchor = chapter_node.select(self.chapter_anchor_selector);
+			let chapter_url = anchor.attr("href").read();
+			if chapter_url.is_empty() {
+				continue;
 			}
 			let date_updated = (self.time_converter)(
 				chapter_node
@@ -294,33 +500,254 @@ impl WPComicsSource {
 					.text()
 					.read(),
 			);
-			chapters.push(Chapter {
-				id: chapter_id,
-				title: String::from(chapter_title.trim()),
-				volume,
-				chapter,
+			chapters.push(self.parse_chapter(
+				manga_title,
+				chapter_url,
+				anchor.text().read(),
 				date_updated,
-				scanlator: String::new(),
-				url: chapter_url,
-				lang: String::from("en"),
-			});
+			));
+		}
+		chapters
+	}
+
+	// the ajax endpoint answers with an html fragment, either as is or wrapped
+	// in a json string (asmx services use `d`), or with a json array of chapters
+	fn parse_chapter_response(&self, manga_title: &str, data: &[u8]) -> Vec<Chapter> {
+		let text = core::str::from_utf8(data).unwrap_or_default().trim_start();
+		if !text.starts_with('{') && !text.starts_with('[') {
+			let fragment = Node::new_fragment(data);
+			return self.parse_chapter_nodes(
+				manga_title,
+				&fragment,
+				self.chapter_ajax_selector,
+				false,
+			);
+		}
+		let json = parse(data);
+		let value = match json.clone().as_object() {
+			Ok(object) => match ["d", "data", "html", "chapters", "list"]
+				.iter()
+				.map(|key| object.get(key))
+				.find(|value| value.clone().as_string().is_ok() || value.clone().as_array().is_ok())
+			{
+				Some(value) => value,
+				None => return Vec::new(),
+			},
+			Err(_) => json,
+		};
+		if let Ok(fragment) = value.clone().as_string() {
+			let fragment = Node::new_fragment(fragment.read().as_bytes());
+			return self.parse_chapter_nodes(
+				manga_title,
+				&fragment,
+				self.chapter_ajax_selector,
+				false,
+			);
+		}
+		let mut chapters: Vec<Chapter> = Vec::new();
+		if let Ok(array) = value.as_array() {
+			for item in array {
+				let object = match item.as_object() {
+					Ok(object) => object,
+					Err(_) => continue,
+				};
+				let field = |keys: &[&str]| {
+					keys.iter()
+						.find_map(|key| object.get(key).as_string().ok())
+						.map(|value| value.read())
+						.unwrap_or_default()
+				};
+				let chapter_url = field(&["url", "chapter_url", "href"]);
+				if chapter_url.is_empty() {
+					continue;
+				}
+				let date_updated = (self.time_converter)(field(&["updated_at", "date", "time"]));
+				chapters.push(self.parse_chapter(
+					manga_title,
+					chapter_url,
+					field(&["name", "chapter_name", "title"]),
+					date_updated,
+				));
+			}
+		}
+		chapters
+	}
+
+	// chapters the details page doesn't render, either behind "load more" links
+	// or served by an ajax endpoint, those already in `seen` are left out
+	fn get_remaining_chapters(
+		&self,
+		manga_title: &str,
+		html: &Node,
+		shown: usize,
+		seen: &mut BTreeSet<String>,
+	) -> Vec<Chapter> {
+		let mut chapters: Vec<Chapter> = Vec::new();
+		let mut keep_new = |found: Vec<Chapter>, chapters: &mut Vec<Chapter>| {
+			let before = chapters.len();
+			chapters.extend(
+				found
+					.into_iter()
+					.filter(|chapter| seen.insert(chapter.id.clone())),
+			);
+			chapters.len() - before
+		};
+		if !self.chapter_load_more.is_empty() {
+			let mut next_url = html.select(self.chapter_load_more).attr("href").read();
+			let mut pages = 0;
+			while !next_url.is_empty() && pages < MAX_CHAPTER_PAGES {
+				if !next_url.starts_with("http") {
+					next_url = format!("{}/{}", self.base_url, next_url.trim_start_matches('/'));
+				}
+				let page = Node::new(&self.request_vinahost(&next_url));
+				// only the details page has the header row `chapter_skip_first` is for
+				let found = self.parse_chapter_nodes(
+					manga_title,
+					&page,
+					self.manga_details_chapters,
+					false,
+				);
+				if keep_new(found, &mut chapters) == 0 {
+					break;
+				}
+				next_url = page.select(self.chapter_load_more).attr("href").read();
+				pages += 1;
+			}
+		}
+		// a details page that isn't full already lists every chapter
+		if !self.chapter_ajax_path.is_empty() && shown + chapters.len() >= self.chapter_ajax_after {
+			let comic_id = html
+				.select(self.chapter_ajax_id_selector)
+				.attr(self.chapter_ajax_id_attr)
+				.read();
+			if comic_id.is_empty() {
+				return chapters;
+			}
+			let paginated = self.chapter_ajax_path.contains("{page}");
+			for page in 1..=MAX_CHAPTER_PAGES {
+				let url = format!(
+					"{}/{}",
+					self.base_url,
+					self.chapter_ajax_path
+						.replace("{id}", &comic_id)
+						.replace("{page}", &format!("{page}"))
+				);
+				let found = self.parse_chapter_response(manga_title, &self.request_vinahost(&url));
+				if keep_new(found, &mut chapters) == 0 || !paginated {
+					break;
+				}
+			}
+		}
+		chapters
+	}
+
+	pub fn get_chapter_list(&self, id: String) -> Result<Vec<Chapter>> {
+		cache_manga_page(self, id.as_str());
+		let html = unsafe { Node::new(&CACHED_MANGA.clone().unwrap()) };
+		let title_untrimmed = (self.manga_details_title_transformer)(
+			html.select(self.manga_details_title).text().read(),
+		);
+		let title = title_untrimmed.trim();
+		let mut chapters = self.parse_chapter_nodes(
+			title,
+			&html,
+			self.manga_details_chapters,
+			self.chapter_skip_first,
+		);
+
+		let mut seen = chapters
+			.iter()
+			.map(|chapter| chapter.id.clone())
+			.collect::<BTreeSet<_>>();
+		let remaining = self.get_remaining_chapters(title, &html, chapters.len(), &mut seen);
+		if !remaining.is_empty() {
+			chapters.extend(remaining);
+			// the endpoints don't agree on an order, keep the newest first
+			sort_newest_first(&mut chapters);
 		}
 		Ok(chapters)
 	}
 
+	// headers the image hosts expect, the same for checks and image loads
+	fn image_headers(&self) -> Vec<(&'static str, String)> {
+		let mut headers: Vec<(&'static str, String)> = Vec::new();
+		headers.push(("Referer", format!("{}/", self.base_url)));
+		if !self.user_agent.is_empty() {
+			headers.push(("User-Agent", String::from(self.user_agent)));
+		}
+		if let Some(cookie) = unsafe { VINAHOST_COOKIE.clone() } {
+			headers.push(("Cookie", cookie));
+		}
+		headers
+	}
+
+	fn server_setting(&self) -> i64 {
+		if self.server_selection_key.is_empty() {
+			return 1;
+		}
+		defaults_get(self.server_selection_key)
+			.as_int()
+			.unwrap_or(1)
+	}
+
+	// server tried first: the last one that worked, else the one picked in
+	// the settings
+	fn preferred_server(&self) -> usize {
+		let setting = self.server_setting();
+		match unsafe { PREFERRED_SERVER } {
+			Some((found_under, server)) if found_under == setting => server,
+			_ if setting == 2 => self.manga_viewer_page_attrs.len(),
+			_ => 0,
+		}
+	}
+
+	// candidate urls of a page, indexed by server
+	fn page_candidates(&self, page_node: &Node) -> Vec<Option<String>> {
+		let mut candidates: Vec<Option<String>> = Vec::new();
+		for attr in self.manga_viewer_page_attrs {
+			let url = String::from(page_node.attr(attr).read().trim());
+			if url.is_empty() || url.starts_with("data:") {
+				candidates.push(None);
+				continue;
+			}
+			let url = (self.page_url_transformer)(append_protocol(url));
+			if candidates.iter().any(|c| c.as_ref() == Some(&url)) {
+				candidates.push(None);
+			} else {
+				candidates.push(Some(url));
+			}
+		}
+		if let Some(primary) = candidates.iter().flatten().next() {
+			for url in (self.page_url_fallbacks)(primary.clone()) {
+				candidates.push(Some(url));
+			}
+		}
+		candidates
+	}
+
 	pub fn get_page_list(&self, id: String) -> Result<Vec<Page>> {
-		let mut pages: Vec<Page> = Vec::new();
 		let url = format!("{}{}", &id, self.manga_viewer_page_url_suffix);
-		let html = self.request_vinahost(&url).html();
-		for (at, page) in html.select(self.manga_viewer_page).array().enumerate() {
-			let page_node = page.as_node();
-			let mut page_url = page_node.attr("data-original").read();
-			if !page_url.starts_with("http") {
-				page_url = String::from("https:") + &page_url;
+		let html = Node::new(&self.request_vinahost(&url));
+		let preferred = self.preferred_server();
+		let nodes = html.select(self.manga_viewer_page).array();
+		let mut pages: Vec<Page> = Vec::with_capacity(nodes.len());
+		for (at, page) in nodes.enumerate() {
+			let candidates = self.page_candidates(&page.as_node());
+			let page_url = by_preference(&candidates, preferred)
+				.next()
+				.map(|(_, url)| url.clone())
+				.unwrap_or_default();
+			if candidates.iter().flatten().count() > 1 {
+				unsafe {
+					if PAGE_CANDIDATES.len() >= MAX_PAGE_CANDIDATES {
+						PAGE_CANDIDATES.remove(0);
+					}
+					PAGE_CANDIDATES.push(candidates);
+				}
 			}
 			pages.push(Page {
 				index: at as i32,
-				url: (self.page_url_transformer)(page_url),
+				url: page_url,
 				base64: String::new(),
 				text: String::new(),
 			});
@@ -364,8 +791,41 @@ impl WPComicsSource {
 		}
 	}
 
+	// adds the image headers, and for pages with more than one server checks
+	// the image first and swaps in the first server that has it
 	pub fn modify_image_request(&self, request: Request) {
-		request.header("Referer", format!("{}/", self.base_url).as_str());
+		let headers = self.image_headers();
+		let request = headers.iter().fold(request, |request, (key, value)| {
+			request.header(*key, value.as_str())
+		});
+		let url = match request_url(&request) {
+			Some(url) => url,
+			None => return,
+		};
+		let candidates = unsafe {
+			PAGE_CANDIDATES
+				.iter()
+				.rev()
+				.find(|candidates| candidates.iter().flatten().any(|c| *c == url))
+				.cloned()
+		};
+		let candidates = match candidates {
+			Some(candidates) => candidates,
+			None => return,
+		};
+		let headers = headers
+			.iter()
+			.map(|(key, value)| (*key, value.as_str()))
+			.collect::<Vec<_>>();
+		for (server, candidate) in by_preference(&candidates, self.preferred_server()) {
+			if image_available(candidate, &headers) {
+				unsafe { PREFERRED_SERVER = Some((self.server_setting(), server)) };
+				if *candidate != url {
+					set_request_url(&request, candidate);
+				}
+				return;
+			}
+		}
 	}
 }
 
@@ -415,18 +875,55 @@ impl Default for WPComicsSource {
 			chapter_skip_first: false,
 			chapter_anchor_selector: "div.chapter > a",
 			chapter_date_selector: "div.col-xs-4",
+			chapter_load_more: "",
+			chapter_ajax_path: "",
+			chapter_ajax_id_selector: "",
+			chapter_ajax_id_attr: "data-id",
+			chapter_ajax_selector: "li",
+			chapter_ajax_after: 50,
 
 			manga_viewer_page: "div.page-chapter > img",
+			manga_viewer_page_attrs: &["data-original", "data-sv1", "data-sv2", "data-cdn"],
 			manga_viewer_page_url_suffix: "",
 			page_url_transformer: |url| url,
+			page_url_fallbacks: |_| Vec::new(),
+			server_selection_key: "",
+			user_agent: "",
 
 			vinahost_protection: false,
+
+			search: SearchDescriptor::default(),
 		}
 	}
 }
 
-pub fn modify_image_request(base_url: String, user_agent: String, request: Request) {
-	request
-		.header("Referer", &base_url)
-		.header("User-Agent", &user_agent);
+// candidates of a page with their server, starting from `preferred`
+fn by_preference(
+	candidates: &[Option<String>],
+	preferred: usize,
+) -> impl Iterator<Item = (usize, &String)> {
+	let (before, after) = candidates.split_at(preferred.min(candidates.len()));
+	after
+		.iter()
+		.enumerate()
+		.map(move |(at, url)| (at + before.len(), url))
+		.chain(before.iter().enumerate())
+		.filter_map(|(server, url)| url.as_ref().map(|url| (server, url)))
+}
+
+// newest first by chapter number, a chapter without one (extras, one-shots)
+// takes the number of the chapter listed before it so it stays next to it
+fn sort_newest_first(chapters: &mut Vec<Chapter>) {
+	let mut last = f32::MAX;
+	let mut keyed = chapters
+		.drain(..)
+		.map(|chapter| {
+			if chapter.chapter >= 0.0 {
+				last = chapter.chapter;
+			}
+			(last, chapter)
+		})
+		.collect::<Vec<_>>();
+	keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+	chapters.extend(keyed.into_iter().map(|(_, chapter)| chapter));
 }